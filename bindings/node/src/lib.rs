@@ -0,0 +1,80 @@
+//! Node.js bindings for the `luhn` crate, via napi-rs.
+
+use std::ops::Range;
+
+/// Validates the given string of ASCII digits using the Luhn algorithm.
+/// Empty input, or anything other than digits, is invalid.
+fn valid(s: &str) -> bool {
+    valid_many(&[s])[0]
+}
+
+/// Validates each of the given strings by the same rules as [`valid`],
+/// via [`luhn::valid_many`].
+fn valid_many<S: AsRef<str>>(values: &[S]) -> Vec<bool> {
+    let mut buffer = Vec::new();
+    let offsets: Vec<Range<usize>> = values
+        .iter()
+        .map(|s| {
+            let start = buffer.len();
+            buffer.extend_from_slice(s.as_ref().as_bytes());
+            start..buffer.len()
+        })
+        .collect();
+    let mut out = vec![false; values.len()];
+    luhn::valid_many(&buffer, &offsets, &mut out);
+    out
+}
+
+// Node.js provides the N-API symbols when it loads the library, so the
+// exports are left out of test builds, which would fail to link on some
+// platforms.
+#[cfg(not(test))]
+mod exports {
+    use napi::{Error, Result};
+    use napi_derive::napi;
+
+    /// Validates the given string of ASCII digits using the Luhn algorithm.
+    /// Empty input, or anything other than digits, is invalid.
+    #[napi]
+    pub fn valid(s: String) -> bool {
+        super::valid(&s)
+    }
+
+    /// Validates each of the given strings, in a single call across the
+    /// JavaScript/native boundary.
+    #[napi]
+    pub fn valid_many(values: Vec<String>) -> Vec<bool> {
+        super::valid_many(&values)
+    }
+
+    /// Computes the check digit for the given uppercase alphanumeric string.
+    #[napi]
+    pub fn checksum(s: String) -> Result<String> {
+        match luhn::try_checksum(s.as_bytes()) {
+            Ok(check) => Ok((check as char).to_string()),
+            Err(e) => Err(Error::from_reason(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_strictly() {
+        assert!(valid("4111111111111111"));
+        assert!(!valid("4111111111111112"));
+        assert!(!valid(""));
+        assert!(!valid("abc"));
+        assert!(!valid("4111 1111 1111 1111"));
+    }
+
+    #[test]
+    fn validates_many() {
+        let values = ["79927398713", "", "18", "4111-1111", "0"];
+        assert_eq!(valid_many(&values), [true, false, true, false, true]);
+        let none: [&str; 0] = [];
+        assert!(valid_many(&none).is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+"""Luhn check-digit expressions for Polars.
+
+    import polars as pl
+    from luhn_polars import luhn_valid, luhn_checksum
+
+    df.with_columns(luhn_valid("card").alias("card_ok"))
+
+The same expressions are available under the ``luhn`` namespace, as
+``pl.col("card").luhn.valid()`` and ``pl.col("card").luhn.checksum()``.
+"""
+
+from pathlib import Path
+
+import polars as pl
+from polars.plugins import register_plugin_function
+
+_LIB = Path(__file__).parent
+
+__all__ = ["luhn_valid", "luhn_checksum"]
+
+
+def luhn_valid(expr):
+    """Whether each string is all digits, ending in a good check digit."""
+    return register_plugin_function(
+        plugin_path=_LIB,
+        function_name="luhn_valid",
+        args=expr,
+        is_elementwise=True,
+    )
+
+
+def luhn_checksum(expr):
+    """The check digit for each uppercase alphanumeric string, or null."""
+    return register_plugin_function(
+        plugin_path=_LIB,
+        function_name="luhn_checksum",
+        args=expr,
+        is_elementwise=True,
+    )
+
+
+@pl.api.register_expr_namespace("luhn")
+class LuhnNamespace:
+    def __init__(self, expr):
+        self._expr = expr
+
+    def valid(self):
+        return luhn_valid(self._expr)
+
+    def checksum(self):
+        return luhn_checksum(self._expr)
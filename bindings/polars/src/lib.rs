@@ -0,0 +1,39 @@
+//! Polars expression plugin for the `luhn` crate.
+//!
+//! The Python side, in `python/luhn_polars`, registers these as the
+//! `luhn_valid` and `luhn_checksum` expressions.
+
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3_polars::derive::polars_expr;
+
+/// Whether each string is all ASCII digits, ending in a good check digit.
+#[polars_expr(output_type = Boolean)]
+fn luhn_valid(inputs: &[Series]) -> PolarsResult<Series> {
+    let ca = inputs[0].str()?;
+    let out: BooleanChunked = ca.iter().map(|s| s.map(valid)).collect();
+    Ok(out.with_name(ca.name().clone()).into_series())
+}
+
+/// The check digit for each uppercase alphanumeric string, or null for
+/// anything else.
+#[polars_expr(output_type = String)]
+fn luhn_checksum(inputs: &[Series]) -> PolarsResult<Series> {
+    let ca = inputs[0].str()?;
+    let out: StringChunked = ca.iter().map(|s| s.and_then(checksum)).collect();
+    Ok(out.with_name(ca.name().clone()).into_series())
+}
+
+fn valid(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) && luhn::valid(s)
+}
+
+fn checksum(s: &str) -> Option<String> {
+    let check = luhn::try_checksum(s.as_bytes()).ok()?;
+    Some((check as char).to_string())
+}
+
+#[pymodule]
+fn _internal(_m: &Bound<'_, PyModule>) -> PyResult<()> {
+    Ok(())
+}
@@ -0,0 +1,471 @@
+//! A command-line interface to the `luhn` crate.
+
+use std::cell::Cell;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use luhn::scan::{Filters, Match, PrefixFilter, ScanConfig};
+
+use crate::output::{CheckRecord, Format, GeneratedRecord, Mode, Output, ScanRecord};
+
+mod output;
+mod scan;
+
+#[derive(Parser)]
+#[command(
+    name = "luhn",
+    version,
+    about = "Validates and computes Luhn check digits",
+    after_help = "Exit status: 0 if everything checked was valid, or scan found \
+                  nothing; 1 if something wasn't, or scan found something; 2 on \
+                  a usage or I/O error."
+)]
+struct Cli {
+    /// How to write results.
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check whether a value validates.  Exits 1 if it doesn't.
+    ///
+    /// Pass `-` to check each line of standard input instead; the exit
+    /// status is then 1 if any line fails.
+    Check {
+        value: String,
+        #[command(flatten)]
+        mode: ModeOptions,
+    },
+    /// Print a value with its check digit appended.
+    Append { value: String },
+    /// Generate random valid numbers.
+    Generate {
+        /// The length of each number, including the check digit.
+        #[arg(long, default_value_t = 16)]
+        len: usize,
+        /// The digits each number starts with.
+        #[arg(long, default_value = "")]
+        prefix: String,
+        /// How many numbers to generate.
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+        /// Generate numbers in this format instead, with `#` for a random
+        /// digit, `?` for a random digit or uppercase letter, `C` for the
+        /// check digit and anything else as written, eg.
+        /// "4111-####-####-###C".  A backslash escapes the next character.
+        #[arg(long, conflicts_with_all = ["len", "prefix"])]
+        template: Option<String>,
+    },
+    /// Search files for card numbers, reporting each one masked.
+    ///
+    /// Directories are searched recursively.  Exits 1 if anything
+    /// is found.
+    Scan {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        #[command(flatten)]
+        options: ScanOptions,
+        #[command(flatten)]
+        mode: ModeOptions,
+    },
+    /// Print a completion script for a shell.
+    ///
+    /// For example, `luhn completions bash > /etc/bash_completion.d/luhn`.
+    Completions { shell: Shell },
+    /// Write man pages for `luhn` and each of its subcommands to a
+    /// directory.
+    Manpages { dir: PathBuf },
+}
+
+/// How much `check` and `scan` write.
+#[derive(Args)]
+struct ModeOptions {
+    /// Write no results, only setting the exit status.
+    #[arg(long, short, conflicts_with = "count_only")]
+    quiet: bool,
+    /// Write only the number of invalid values checked, or of card numbers
+    /// found.
+    #[arg(long)]
+    count_only: bool,
+}
+
+impl ModeOptions {
+    fn mode(&self) -> Mode {
+        if self.quiet {
+            Mode::Quiet
+        } else if self.count_only {
+            Mode::Count
+        } else {
+            Mode::All
+        }
+    }
+}
+
+#[derive(Args)]
+struct ScanOptions {
+    /// The fewest digits a number may have.
+    #[arg(long, default_value_t = 12)]
+    min_len: usize,
+    /// The most digits a number may have.
+    #[arg(long, default_value_t = 19)]
+    max_len: usize,
+    /// The characters which may separate digits.  Pass an empty string to
+    /// only find unbroken runs of digits.
+    #[arg(long, default_value = " -")]
+    separators: String,
+    /// Only report numbers starting with this prefix.  May be repeated.
+    #[arg(long, value_name = "PREFIX", conflicts_with = "deny_prefix")]
+    allow_prefix: Vec<String>,
+    /// Don't report numbers starting with this prefix.  May be repeated.
+    #[arg(long, value_name = "PREFIX")]
+    deny_prefix: Vec<String>,
+    /// Discard a kind of likely false positive.  May be repeated.
+    #[arg(long, value_enum, value_name = "FILTER")]
+    filter: Vec<FilterName>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FilterName {
+    /// Every filter below.
+    All,
+    /// Numbers whose digits are all the same, check digit aside.
+    RepeatedDigits,
+    /// Numbers whose digits count up, check digit aside.
+    SequentialDigits,
+    /// Well-known public test card numbers.
+    TestCards,
+    /// Numbers shaped like Unix times or compact dates and times.
+    Timestamps,
+    /// Numbers just after a `+` or a word like "tel" or "phone".
+    PhoneNumbers,
+}
+
+impl ScanOptions {
+    fn config(self) -> Result<ScanConfig, String> {
+        if self.min_len > self.max_len {
+            return Err("--min-len must not be more than --max-len".to_string());
+        }
+        if let Some(c) = self
+            .separators
+            .chars()
+            .find(|c| !c.is_ascii() || c.is_ascii_digit())
+        {
+            return Err(format!("not a usable separator: {:?}", c));
+        }
+        let prefixes = if !self.allow_prefix.is_empty() {
+            PrefixFilter::Allow(self.allow_prefix)
+        } else if !self.deny_prefix.is_empty() {
+            PrefixFilter::Deny(self.deny_prefix)
+        } else {
+            PrefixFilter::Any
+        };
+        let mut filters = Filters::default();
+        for name in self.filter {
+            match name {
+                FilterName::All => filters = Filters::all(),
+                FilterName::RepeatedDigits => filters.repeated_digits = true,
+                FilterName::SequentialDigits => filters.sequential_digits = true,
+                FilterName::TestCards => filters.test_cards = true,
+                FilterName::Timestamps => filters.timestamps = true,
+                FilterName::PhoneNumbers => filters.phone_numbers = true,
+            }
+        }
+        Ok(ScanConfig {
+            min_len: self.min_len,
+            max_len: self.max_len,
+            separators: self.separators.into_bytes(),
+            prefixes,
+            filters,
+        })
+    }
+}
+
+/// Everything checked was valid, or a scan found nothing.
+const EXIT_OK: i32 = 0;
+/// Something checked was invalid, or a scan found a card number.
+const EXIT_FOUND: i32 = 1;
+/// The arguments were wrong or something couldn't be read or written.
+/// Clap uses the same status for usage errors.
+const EXIT_ERROR: i32 = 2;
+
+fn main() {
+    let cli = Cli::parse();
+    let format = cli.format;
+    let result = match cli.command {
+        Command::Check { value, mode } => check(&value, format, mode.mode()),
+        Command::Append { value } => append(&value, format),
+        Command::Generate {
+            count,
+            template: Some(template),
+            ..
+        } => generate_from_template(&template, count, format),
+        Command::Generate {
+            len,
+            prefix,
+            count,
+            template: None,
+        } => generate(len, &prefix, count, format),
+        Command::Scan {
+            paths,
+            options,
+            mode,
+        } => options
+            .config()
+            .and_then(|config| scan(&paths, &config, format, mode.mode())),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "luhn", &mut io::stdout());
+            Ok(EXIT_OK)
+        }
+        Command::Manpages { dir } => clap_mangen::generate_to(Cli::command(), &dir)
+            .map(|()| EXIT_OK)
+            .map_err(|e| format!("{}: {}", dir.display(), e)),
+    };
+    match result {
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            eprintln!("luhn: {}", err);
+            process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn check(value: &str, format: Format, mode: Mode) -> Result<i32, String> {
+    let stdout = io::stdout();
+    let mut out = Output::new(format, BufWriter::new(stdout.lock())).mode(mode);
+    let all_valid = if value == "-" {
+        check_lines(io::stdin().lock(), &mut out)
+    } else {
+        check_one(value, &mut out)
+    }
+    .and_then(|valid| out.finish().map(|()| valid))
+    .map_err(|e| e.to_string())?;
+    Ok(if all_valid { EXIT_OK } else { EXIT_FOUND })
+}
+
+/// Checks each line of `input`, returning whether they all validated.
+fn check_lines<W: Write>(input: impl BufRead, out: &mut Output<W>) -> io::Result<bool> {
+    let mut all_valid = true;
+    for line in input.lines() {
+        let line = line?;
+        let value = line.trim_end_matches('\r');
+        all_valid &= check_one(value, out)?;
+    }
+    Ok(all_valid)
+}
+
+fn check_one<W: Write>(value: &str, out: &mut Output<W>) -> io::Result<bool> {
+    let record = CheckRecord::new(value);
+    out.check(&record)?;
+    Ok(record.valid)
+}
+
+fn append(value: &str, format: Format) -> Result<i32, String> {
+    let check = luhn::try_checksum(value.as_bytes()).map_err(|e| e.to_string())?;
+    let appended = format!("{}{}", value, check as char);
+    let stdout = io::stdout();
+    let mut out = Output::new(format, stdout.lock());
+    out.appended(&CheckRecord::new(&appended))
+        .and_then(|()| out.finish())
+        .map_err(|e| e.to_string())?;
+    Ok(EXIT_OK)
+}
+
+fn generate(len: usize, prefix: &str, count: usize, format: Format) -> Result<i32, String> {
+    if !prefix.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("not a decimal prefix: {:?}", prefix));
+    }
+    if prefix.len() >= len {
+        return Err(format!(
+            "--len {} leaves no room for a check digit after {:?}",
+            len, prefix
+        ));
+    }
+    let mut rng = rand::thread_rng();
+    write_generated(count, format, || {
+        luhn::generate_with_prefix(prefix, len, &mut rng)
+    })
+}
+
+fn generate_from_template(template: &str, count: usize, format: Format) -> Result<i32, String> {
+    let mut rng = rand::thread_rng();
+    // Checked up front so that nothing is written for a bad template.
+    luhn::generate_from_template(template, &mut rng).map_err(|e| e.to_string())?;
+    write_generated(count, format, || {
+        luhn::generate_from_template(template, &mut rng).unwrap()
+    })
+}
+
+/// Writes `count` values made by `next`.
+fn write_generated(
+    count: usize,
+    format: Format,
+    mut next: impl FnMut() -> String,
+) -> Result<i32, String> {
+    let stdout = io::stdout();
+    let mut out = Output::new(format, BufWriter::new(stdout.lock()));
+    (0..count)
+        .try_for_each(|_| out.generated(&GeneratedRecord { value: &next() }))
+        .and_then(|()| out.finish())
+        .map_err(|e| e.to_string())?;
+    Ok(EXIT_OK)
+}
+
+/// Scans everything under `paths`.  Files which can't be read are
+/// reported and skipped, but make the exit status [`EXIT_ERROR`].
+fn scan(paths: &[PathBuf], config: &ScanConfig, format: Format, mode: Mode) -> Result<i32, String> {
+    let stdout = io::stdout();
+    let mut out = Output::new(format, BufWriter::new(stdout.lock())).mode(mode);
+    let mut any_found = false;
+    let any_error = Cell::new(false);
+    let report = |path: &Path, err: io::Error| {
+        eprintln!("luhn: {}: {}", path.display(), err);
+        any_error.set(true);
+    };
+    let mut visit = |path: &Path| -> io::Result<()> {
+        let found = match find(path, config) {
+            Ok(found) => found,
+            Err(err) => {
+                report(path, err);
+                return Ok(());
+            }
+        };
+        for found in found {
+            any_found = true;
+            out.scan(&ScanRecord {
+                file: &path.to_string_lossy(),
+                offset: found.range.start,
+                masked: &mask(&found.digits),
+            })?;
+        }
+        Ok(())
+    };
+    let mut on_error = report;
+    for path in paths {
+        scan::walk(path, &mut visit, &mut on_error).map_err(|e| e.to_string())?;
+    }
+    out.finish().map_err(|e| e.to_string())?;
+    Ok(if any_error.get() {
+        EXIT_ERROR
+    } else if any_found {
+        EXIT_FOUND
+    } else {
+        EXIT_OK
+    })
+}
+
+/// Finds the card numbers in the file at `path`, memory-mapping it and
+/// splitting the work across threads.
+#[cfg(not(target_os = "wasi"))]
+fn find(path: &Path, config: &ScanConfig) -> io::Result<Vec<Match>> {
+    luhn::validate_file_parallel(path, &luhn::FileConfig::Scan(config.clone()))
+        .map(|report| report.matches)
+}
+
+/// Finds the card numbers in the file at `path`, reading it in pieces.
+#[cfg(target_os = "wasi")]
+fn find(path: &Path, config: &ScanConfig) -> io::Result<Vec<Match>> {
+    luhn::scan::Scanner::new(std::fs::File::open(path)?, config.clone()).collect()
+}
+
+/// Masks all but the last four digits.
+fn mask(digits: &str) -> String {
+    let keep = digits.len().saturating_sub(4);
+    let mut masked = "*".repeat(keep);
+    masked.push_str(&digits[keep..]);
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_is_strict() {
+        let mut out = Output::new(Format::Plain, Vec::new());
+        assert!(check_one("4111111111111111", &mut out).unwrap());
+        assert!(!check_one("4111 1111 1111 1111", &mut out).unwrap());
+        assert!(!check_one("", &mut out).unwrap());
+    }
+
+    #[test]
+    fn check_accepts_what_append_produces() {
+        let mut out = Output::new(Format::Plain, Vec::new());
+        assert!(check_one("US0378331005", &mut out).unwrap());
+        assert!(!check_one("US0378331006", &mut out).unwrap());
+        assert!(!check_one("us0378331005", &mut out).unwrap());
+    }
+
+    #[test]
+    fn check_lines_fails_if_any_line_does() {
+        let mut out = Output::new(Format::Plain, Vec::new());
+        let input: &[u8] = b"4111111111111111\r\n4111111111111112\n79927398713\n";
+        assert!(!check_lines(input, &mut out).unwrap());
+        assert_eq!(
+            String::from_utf8(out.into_inner()).unwrap(),
+            "4111111111111111: valid\n4111111111111112: invalid\n79927398713: valid\n"
+        );
+
+        let input: &[u8] = b"4111111111111111\n79927398713\n";
+        let mut out = Output::new(Format::Plain, Vec::new());
+        assert!(check_lines(input, &mut out).unwrap());
+    }
+
+    #[test]
+    fn usage_errors_exit_with_error_status() {
+        Cli::command().debug_assert();
+        let err = Cli::try_parse_from(["luhn", "check"]).err().unwrap();
+        assert_eq!(err.exit_code(), EXIT_ERROR);
+        let err = Cli::try_parse_from(["luhn", "check", "-q", "--count-only", "18"])
+            .err()
+            .unwrap();
+        assert_eq!(err.exit_code(), EXIT_ERROR);
+        assert!(Cli::try_parse_from(["luhn", "check", "18", "--quiet"]).is_ok());
+        assert!(Cli::try_parse_from(["luhn", "scan", "logs", "--count-only"]).is_ok());
+        // Nothing else writes results which could be counted or left out.
+        assert!(Cli::try_parse_from(["luhn", "append", "7", "--quiet"]).is_err());
+        assert!(Cli::try_parse_from(["luhn", "--count-only", "generate"]).is_err());
+    }
+
+    #[test]
+    fn writes_man_pages() {
+        let dir = std::env::temp_dir().join(format!("luhn-cli-man-{}", process::id()));
+        std::fs::create_dir(&dir).unwrap();
+        let written = clap_mangen::generate_to(Cli::command(), &dir).map(|()| {
+            let mut names: Vec<_> = std::fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect();
+            names.sort();
+            names
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            written.unwrap(),
+            [
+                "luhn-append.1",
+                "luhn-check.1",
+                "luhn-completions.1",
+                "luhn-generate.1",
+                "luhn-manpages.1",
+                "luhn-scan.1",
+                "luhn.1"
+            ]
+        );
+    }
+
+    #[test]
+    fn find_reports_offsets() {
+        let path = std::env::temp_dir().join(format!("luhn-cli-find-{}", process::id()));
+        std::fs::write(&path, "card 4111 1111 1111 1111, ref 79927398713\n").unwrap();
+        let found = find(&path, &ScanConfig::default());
+        std::fs::remove_file(&path).unwrap();
+        let found: Vec<_> = found.unwrap().into_iter().map(|m| m.range.start).collect();
+        assert_eq!(found, [5]);
+    }
+}
@@ -0,0 +1,348 @@
+//! Writing results as plain text, JSON lines or CSV.
+
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How results are written to standard output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One human-readable line per result.
+    #[default]
+    Plain,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+/// How much of the results is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Every record.
+    #[default]
+    All,
+    /// Just the number of invalid values, or of card numbers found.
+    Count,
+    /// Nothing; the exit status says how it went.
+    Quiet,
+}
+
+/// The outcome of checking a single value.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct CheckRecord<'a> {
+    pub value: &'a str,
+    pub valid: bool,
+    /// Why the value failed, if it did.
+    pub reason: Option<String>,
+    /// The check digit the value should end in, if it's uppercase
+    /// alphanumeric.
+    pub check_digit: Option<char>,
+}
+
+impl<'a> CheckRecord<'a> {
+    pub fn new(value: &'a str) -> Self {
+        let result = luhn::validate_alphanumeric(value);
+        let check_digit = match result {
+            Ok(()) => value.chars().next_back(),
+            Err(luhn::LuhnError::BadCheckDigit { expected, .. }) => Some(expected),
+            Err(_) => None,
+        };
+        CheckRecord {
+            value,
+            valid: result.is_ok(),
+            reason: result.err().map(|err| err.to_string()),
+            check_digit,
+        }
+    }
+}
+
+/// A number made by `luhn generate`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct GeneratedRecord<'a> {
+    pub value: &'a str,
+}
+
+/// A card number found by `luhn scan`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct ScanRecord<'a> {
+    pub file: &'a str,
+    /// The byte offset of the first digit.
+    pub offset: usize,
+    /// The number with all but the last four digits masked.
+    pub masked: &'a str,
+}
+
+/// Writes records in the chosen format, emitting a CSV header before the
+/// first one.  Each `Output` should only be given one kind of record.
+pub struct Output<W> {
+    format: Format,
+    mode: Mode,
+    out: W,
+    header_written: bool,
+    /// The invalid values checked, or the card numbers found.
+    count: u64,
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Output {
+            format,
+            mode: Mode::All,
+            out,
+            header_written: false,
+            count: 0,
+        }
+    }
+
+    pub fn mode(self, mode: Mode) -> Self {
+        Output { mode, ..self }
+    }
+
+    pub fn check(&mut self, record: &CheckRecord) -> io::Result<()> {
+        if !record.valid {
+            self.count += 1;
+        }
+        if self.mode != Mode::All {
+            return Ok(());
+        }
+        match self.format {
+            Format::Plain => {
+                let result = if record.valid { "valid" } else { "invalid" };
+                writeln!(self.out, "{}: {}", record.value, result)
+            }
+            Format::Json => {
+                serde_json::to_writer(&mut self.out, record)?;
+                writeln!(self.out)
+            }
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "value,valid,reason,check_digit")?;
+                    self.header_written = true;
+                }
+                writeln!(
+                    self.out,
+                    "{},{},{},{}",
+                    csv_field(record.value),
+                    record.valid,
+                    csv_field(record.reason.as_deref().unwrap_or("")),
+                    record.check_digit.map(String::from).unwrap_or_default(),
+                )
+            }
+        }
+    }
+
+    /// Writes the result of `luhn append`, which is on its own line in
+    /// plain text and a check record otherwise.
+    pub fn appended(&mut self, record: &CheckRecord) -> io::Result<()> {
+        match self.format {
+            Format::Plain => writeln!(self.out, "{}", record.value),
+            Format::Json | Format::Csv => self.check(record),
+        }
+    }
+
+    pub fn generated(&mut self, record: &GeneratedRecord) -> io::Result<()> {
+        match self.format {
+            Format::Plain => writeln!(self.out, "{}", record.value),
+            Format::Json => {
+                serde_json::to_writer(&mut self.out, record)?;
+                writeln!(self.out)
+            }
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "value")?;
+                    self.header_written = true;
+                }
+                writeln!(self.out, "{}", csv_field(record.value))
+            }
+        }
+    }
+
+    pub fn scan(&mut self, record: &ScanRecord) -> io::Result<()> {
+        self.count += 1;
+        if self.mode != Mode::All {
+            return Ok(());
+        }
+        match self.format {
+            Format::Plain => writeln!(
+                self.out,
+                "{}:{}: {}",
+                record.file, record.offset, record.masked
+            ),
+            Format::Json => {
+                serde_json::to_writer(&mut self.out, record)?;
+                writeln!(self.out)
+            }
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "file,offset,masked")?;
+                    self.header_written = true;
+                }
+                writeln!(
+                    self.out,
+                    "{},{},{}",
+                    csv_field(record.file),
+                    record.offset,
+                    record.masked
+                )
+            }
+        }
+    }
+
+    /// Writes the count, if that's all that's wanted, and flushes.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.mode == Mode::Count {
+            writeln!(self.out, "{}", self.count)?;
+        }
+        self.out.flush()
+    }
+
+    #[cfg(test)]
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Quotes a CSV field if it needs it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: Format, values: &[&str]) -> String {
+        let mut output = Output::new(format, Vec::new());
+        for value in values {
+            output.check(&CheckRecord::new(value)).unwrap();
+        }
+        String::from_utf8(output.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn records() {
+        let record = CheckRecord::new("4111111111111112");
+        assert!(!record.valid);
+        assert_eq!(record.check_digit, Some('1'));
+        assert_eq!(
+            record.reason.as_deref(),
+            Some("bad check digit '2' at position 15, expected '1'")
+        );
+
+        let record = CheckRecord::new("4111-1111");
+        assert_eq!(record.check_digit, None);
+        assert_eq!(
+            record.reason.as_deref(),
+            Some("invalid character '-' at position 4")
+        );
+
+        let record = CheckRecord::new("79927398713");
+        assert!(record.valid);
+        assert_eq!(record.reason, None);
+        assert_eq!(record.check_digit, Some('3'));
+
+        let record = CheckRecord::new("US0378331005");
+        assert!(record.valid);
+        assert_eq!(record.check_digit, Some('5'));
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            render(Format::Json, &["79927398713", ""]),
+            concat!(
+                r#"{"value":"79927398713","valid":true,"reason":null,"check_digit":"3"}"#,
+                "\n",
+                r#"{"value":"","valid":false,"reason":"too short: 0 characters, expected at least 1","check_digit":null}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn scan_records() {
+        let record = ScanRecord {
+            file: "logs/a,b.txt",
+            offset: 5,
+            masked: "************1111",
+        };
+        let mut output = Output::new(Format::Csv, Vec::new());
+        output.scan(&record).unwrap();
+        assert_eq!(
+            String::from_utf8(output.into_inner()).unwrap(),
+            "file,offset,masked\n\"logs/a,b.txt\",5,************1111\n"
+        );
+
+        let mut output = Output::new(Format::Plain, Vec::new());
+        output.scan(&record).unwrap();
+        assert_eq!(
+            String::from_utf8(output.into_inner()).unwrap(),
+            "logs/a,b.txt:5: ************1111\n"
+        );
+    }
+
+    #[test]
+    fn appended_and_generated_records() {
+        let record = CheckRecord::new("US0378331005");
+        let mut output = Output::new(Format::Plain, Vec::new());
+        output.appended(&record).unwrap();
+        assert_eq!(
+            String::from_utf8(output.into_inner()).unwrap(),
+            "US0378331005\n"
+        );
+        let mut output = Output::new(Format::Json, Vec::new());
+        output.appended(&record).unwrap();
+        assert_eq!(
+            String::from_utf8(output.into_inner()).unwrap(),
+            concat!(
+                r#"{"value":"US0378331005","valid":true,"reason":null,"check_digit":"5"}"#,
+                "\n"
+            )
+        );
+
+        let mut output = Output::new(Format::Csv, Vec::new());
+        for value in ["4111-1111", "1,8"].iter() {
+            output.generated(&GeneratedRecord { value }).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output.into_inner()).unwrap(),
+            "value\n4111-1111\n\"1,8\"\n"
+        );
+        let mut output = Output::new(Format::Json, Vec::new());
+        output.generated(&GeneratedRecord { value: "18" }).unwrap();
+        assert_eq!(
+            String::from_utf8(output.into_inner()).unwrap(),
+            "{\"value\":\"18\"}\n"
+        );
+    }
+
+    #[test]
+    fn modes() {
+        let render = |mode| {
+            let mut output = Output::new(Format::Csv, Vec::new()).mode(mode);
+            for value in ["79927398713", "79927398710", ""].iter() {
+                output.check(&CheckRecord::new(value)).unwrap();
+            }
+            output.finish().unwrap();
+            String::from_utf8(output.into_inner()).unwrap()
+        };
+        assert_eq!(render(Mode::Count), "2\n");
+        assert_eq!(render(Mode::Quiet), "");
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            render(Format::Csv, &["79927398713", "1,2"]),
+            "value,valid,reason,check_digit\n\
+             79927398713,true,,3\n\
+             \"1,2\",false,\"invalid character ',' at position 1\",\n"
+        );
+    }
+}
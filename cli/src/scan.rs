@@ -0,0 +1,49 @@
+//! Walking the files to sweep for card numbers.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Calls `visit` with every regular file under `path`, which may itself
+/// be a file.  Symbolic links to directories aren't followed.  Errors
+/// reading individual entries go to `on_error` and don't stop the walk.
+pub fn walk(
+    path: &Path,
+    visit: &mut dyn FnMut(&Path) -> io::Result<()>,
+    on_error: &mut dyn FnMut(&Path, io::Error),
+) -> io::Result<()> {
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                on_error(&path, err);
+                continue;
+            }
+        };
+        if metadata.is_file() {
+            visit(&path)?;
+        } else if metadata.is_dir() && !is_symlink(&path) {
+            match read_dir_sorted(&path) {
+                // Reversed so they come off the stack in order.
+                Ok(entries) => pending.extend(entries.into_iter().rev()),
+                Err(err) => on_error(&path, err),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+fn read_dir_sorted(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
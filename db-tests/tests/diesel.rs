@@ -0,0 +1,54 @@
+//! Round trips through Diesel's SQLite backend.
+
+use diesel::dsl::sql;
+use diesel::sql_types::Text;
+use diesel::sqlite::{Sqlite, SqliteConnection};
+use diesel::{Connection, QueryResult, Queryable, RunQueryDsl};
+use luhn::{Imei, Isin, Pan};
+
+fn connect() -> SqliteConnection {
+    SqliteConnection::establish(":memory:").unwrap()
+}
+
+/// Loads the SQL text literal `literal` as a `T`.
+fn load<T>(literal: &str) -> QueryResult<T>
+where
+    T: Queryable<Text, Sqlite> + 'static,
+{
+    diesel::select(sql::<Text>(literal)).get_result(&mut connect())
+}
+
+#[test]
+fn round_trips() {
+    let pan = Pan::new("4111111111111111").unwrap();
+    let loaded: Pan = diesel::select(sql::<Text>("").bind::<Text, _>(&pan))
+        .get_result(&mut connect())
+        .unwrap();
+    assert_eq!(loaded, pan);
+    assert_eq!(
+        load::<Isin>("'US0378331005'").unwrap().as_str(),
+        "US0378331005"
+    );
+    assert_eq!(
+        load::<Imei>("'490154203237518'").unwrap().as_str(),
+        "490154203237518"
+    );
+}
+
+#[test]
+fn rejects_invalid_text() {
+    use diesel::result::Error::DeserializationError;
+
+    assert!(matches!(
+        load::<Pan>("'4111111111111112'"),
+        Err(DeserializationError(_))
+    ));
+    assert!(matches!(
+        load::<Isin>("'US0378331006'"),
+        Err(DeserializationError(_))
+    ));
+    assert!(matches!(
+        load::<Imei>("'49015420323751'"),
+        Err(DeserializationError(_))
+    ));
+}
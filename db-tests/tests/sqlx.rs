@@ -0,0 +1,51 @@
+//! Round trips through sqlx's SQLite driver.
+
+use luhn::{Imei, Isin, Pan};
+use sqlx::{Connection, Decode, Encode, Sqlite, SqliteConnection, Type};
+
+/// Selects `value` back out of an in-memory SQLite database as a `T`.
+fn select<'q, T>(
+    value: impl Encode<'q, Sqlite> + Type<Sqlite> + Send + 'q,
+) -> Result<T, sqlx::Error>
+where
+    T: for<'r> Decode<'r, Sqlite> + Type<Sqlite> + Send + Unpin,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+        sqlx::query_scalar("SELECT ?")
+            .bind(value)
+            .fetch_one(&mut conn)
+            .await
+    })
+}
+
+#[test]
+fn round_trips() {
+    let pan = Pan::new("4111111111111111").unwrap();
+    assert_eq!(select::<Pan>(pan.clone()).unwrap(), pan);
+    let isin = Isin::new("US0378331005").unwrap();
+    assert_eq!(select::<Isin>(isin.clone()).unwrap(), isin);
+    let imei = Imei::new("490154203237518").unwrap();
+    assert_eq!(select::<Imei>(imei.clone()).unwrap(), imei);
+    assert_eq!(select::<String>(pan).unwrap(), "4111111111111111");
+}
+
+#[test]
+fn rejects_invalid_text() {
+    for result in [
+        select::<Pan>("4111111111111112").map(drop),
+        select::<Isin>("US0378331006").map(drop),
+        select::<Imei>("49015420323751").map(drop),
+    ]
+    .iter()
+    {
+        assert!(
+            matches!(result, Err(sqlx::Error::ColumnDecode { .. })),
+            "{:?}",
+            result
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Choosing a check digit scheme at runtime.
+
+use crate::iso7064::{mod_11_10, mod_97_10};
+use crate::{check_digits, damm, gs1, verhoeff, LuhnError};
+
+/// A check digit scheme, for when which one to use is configuration
+/// rather than code.
+///
+/// ```
+/// use luhn::Algorithm;
+///
+/// let algorithm = Algorithm::Verhoeff;
+/// assert_eq!(algorithm.compute("236").unwrap(), "3");
+/// assert!(algorithm.validate("2363"));
+/// assert!(!Algorithm::Luhn.validate("2363"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// The Luhn algorithm, on decimal digits.
+    Luhn,
+    /// See [`verhoeff`](crate::verhoeff).
+    Verhoeff,
+    /// See [`damm`](crate::damm).
+    Damm,
+    /// See [`gs1`](crate::gs1).
+    Gs1,
+    /// See [`mod_11_10`](crate::iso7064::mod_11_10).
+    Iso7064Mod11_10,
+    /// See [`mod_97_10`](crate::iso7064::mod_97_10), the only scheme here
+    /// with two check digits, and the only one accepting letters.
+    Iso7064Mod97_10,
+}
+
+impl Algorithm {
+    /// Whether `input` ends in good check digits for this scheme.
+    ///
+    /// Unlike [`valid`](crate::valid), the empty string and anything
+    /// containing non-digits are rejected.
+    pub fn validate(&self, input: &str) -> bool {
+        match self {
+            Algorithm::Luhn => check_digits(input).is_ok(),
+            Algorithm::Verhoeff => verhoeff::valid(input),
+            Algorithm::Damm => damm::valid(input),
+            Algorithm::Gs1 => gs1::valid(input),
+            Algorithm::Iso7064Mod11_10 => mod_11_10::valid(input),
+            Algorithm::Iso7064Mod97_10 => mod_97_10::valid(input),
+        }
+    }
+
+    /// Computes the check digits to append to `input`.
+    ///
+    /// Fails with [`LuhnError::InvalidCharacter`] if `input` has
+    /// characters the scheme doesn't allow: anything but ASCII digits, or
+    /// for [`Iso7064Mod97_10`](Algorithm::Iso7064Mod97_10), uppercase
+    /// alphanumeric ASCII.
+    pub fn compute(&self, input: &str) -> Result<String, LuhnError> {
+        let allowed = match self {
+            Algorithm::Iso7064Mod97_10 => |c: char| c.is_ascii_digit() || c.is_ascii_uppercase(),
+            _ => |c: char| c.is_ascii_digit(),
+        };
+        if let Some((position, character)) = input.char_indices().find(|&(_, c)| !allowed(c)) {
+            return Err(LuhnError::InvalidCharacter {
+                position,
+                character,
+            });
+        }
+        let body = input.as_bytes();
+        let check = match self {
+            Algorithm::Luhn => crate::checksum(body),
+            Algorithm::Verhoeff => verhoeff::checksum(body),
+            Algorithm::Damm => damm::checksum(body),
+            Algorithm::Gs1 => gs1::checksum(body),
+            Algorithm::Iso7064Mod11_10 => mod_11_10::checksum(body),
+            Algorithm::Iso7064Mod97_10 => {
+                let [first, second] = mod_97_10::checksum(body);
+                return Ok([first as char, second as char].iter().collect());
+            }
+        };
+        Ok((check as char).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Algorithm; 6] = [
+        Algorithm::Luhn,
+        Algorithm::Verhoeff,
+        Algorithm::Damm,
+        Algorithm::Gs1,
+        Algorithm::Iso7064Mod11_10,
+        Algorithm::Iso7064Mod97_10,
+    ];
+
+    #[test]
+    fn computes_what_validates() {
+        for algorithm in ALL.iter() {
+            for body in ["0", "7992739871", "400638133393"].iter() {
+                let number = format!("{}{}", body, algorithm.compute(body).unwrap());
+                assert!(algorithm.validate(&number), "{:?} {}", algorithm, number);
+            }
+        }
+        assert_eq!(Algorithm::Luhn.compute("7992739871").unwrap(), "3");
+        assert_eq!(Algorithm::Gs1.compute("400638133393").unwrap(), "1");
+        assert_eq!(Algorithm::Iso7064Mod97_10.compute("123456").unwrap(), "76");
+    }
+
+    #[test]
+    fn rejects_bad_characters() {
+        assert_eq!(
+            Algorithm::Damm.compute("12A"),
+            Err(LuhnError::InvalidCharacter {
+                position: 2,
+                character: 'A'
+            })
+        );
+        assert_eq!(Algorithm::Iso7064Mod97_10.compute("12A").unwrap(), "57");
+        assert!(Algorithm::Iso7064Mod97_10.compute("12a").is_err());
+        assert!(!Algorithm::Luhn.validate(""));
+        assert!(!Algorithm::Luhn.validate("7992 7398 713"));
+    }
+}
@@ -0,0 +1,66 @@
+//! Validating Arrow string columns.
+//!
+//! ```
+//! use arrow_array::{Array, StringArray};
+//!
+//! let column = StringArray::from(vec![Some("4111111111111111"), None, Some("1234")]);
+//! let valid = luhn::arrow::valid(&column);
+//! assert!(valid.value(0));
+//! assert!(valid.is_null(1));
+//! assert!(!valid.value(2));
+//! ```
+
+use arrow_array::{Array, BooleanArray, GenericStringArray, OffsetSizeTrait};
+use arrow_buffer::BooleanBuffer;
+
+/// Validates every value in a `StringArray` or `LargeStringArray`.
+///
+/// Values must be all ASCII digits, ending in a good check digit, to be
+/// valid.  Null values stay null: the result shares the input's null
+/// buffer.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = array.len()))
+)]
+pub fn valid<O: OffsetSizeTrait>(array: &GenericStringArray<O>) -> BooleanArray {
+    let values =
+        BooleanBuffer::collect_bool(array.len(), |i| crate::check_digits(array.value(i)).is_ok());
+    BooleanArray::new(values, array.nulls().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{LargeStringArray, StringArray};
+
+    #[test]
+    fn validates_string_arrays() {
+        let array = StringArray::from(vec![
+            Some("4111111111111111"),
+            Some("4111111111111112"),
+            None,
+            Some("4111 1111 1111 1111"),
+            Some(""),
+        ]);
+        let valid = valid(&array);
+        assert_eq!(
+            valid.iter().collect::<Vec<_>>(),
+            [Some(true), Some(false), None, Some(false), Some(false)]
+        );
+    }
+
+    #[test]
+    fn validates_large_string_arrays() {
+        let array = LargeStringArray::from(vec![Some("79927398713"), None]);
+        let valid = valid(&array);
+        assert_eq!(valid.null_count(), 1);
+        assert!(valid.value(0));
+    }
+
+    #[test]
+    fn validates_slices() {
+        let array = StringArray::from(vec![Some("1"), None, Some("79927398713")]);
+        let valid = valid(&array.slice(1, 2));
+        assert_eq!(valid.iter().collect::<Vec<_>>(), [None, Some(true)]);
+    }
+}
@@ -0,0 +1,153 @@
+//! Brazilian taxpayer numbers: CPF for people and CNPJ for companies.
+//!
+//! Both end in two mod-11 check digits, the second covering the first.
+//! Validation accepts the bare digits or the usual punctuated forms, such
+//! as `"123.456.789-09"` and `"11.222.333/0001-81"`.
+//!
+//! ```
+//! use luhn::br;
+//!
+//! assert!(br::cpf_valid("123.456.789-09"));
+//! assert_eq!(&br::cpf_checksum(b"123456789"), b"09");
+//! assert!(br::cnpj_valid("11222333000181"));
+//! assert_eq!(&br::cnpj_checksum(b"112223330001"), b"81");
+//! ```
+
+use crate::small::SmallString;
+
+const CPF_BODY_LEN: usize = 9;
+const CNPJ_BODY_LEN: usize = 12;
+
+/// The weights for a CNPJ's second check digit; the first uses all but
+/// the first of them.
+const CNPJ_WEIGHTS: [u32; 13] = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// Computes the two check digits for the first nine digits of a CPF.
+///
+/// Input must be nine ASCII digits; panics otherwise.  Returns ASCII
+/// digits.
+pub fn cpf_checksum(body: &[u8]) -> [u8; 2] {
+    assert!(
+        body.len() == CPF_BODY_LEN && body.iter().all(u8::is_ascii_digit),
+        "Not a CPF body: {:?}",
+        body
+    );
+    check_digits(body, |i, len| (len + 1 - i) as u32)
+}
+
+/// Computes the two check digits for the first twelve characters of a
+/// CNPJ.
+///
+/// Input must be twelve ASCII digits or uppercase letters (as allowed in
+/// CNPJs issued from July 2026); panics otherwise.  Returns ASCII digits.
+pub fn cnpj_checksum(body: &[u8]) -> [u8; 2] {
+    assert!(
+        body.len() == CNPJ_BODY_LEN
+            && body
+                .iter()
+                .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase()),
+        "Not a CNPJ body: {:?}",
+        body
+    );
+    check_digits(body, |i, len| CNPJ_WEIGHTS[CNPJ_WEIGHTS.len() - len + i])
+}
+
+/// Whether `s` is an 11-digit CPF, bare or punctuated, with good check
+/// digits.
+///
+/// Numbers with all digits the same, such as `"111.111.111-11"`, pass the
+/// check but are never issued, so they're rejected.
+pub fn cpf_valid(s: &str) -> bool {
+    let s = strip_punctuation(s);
+    s.len() == CPF_BODY_LEN + 2
+        && s.bytes().all(|b| b.is_ascii_digit())
+        && !all_same(&s)
+        && cpf_checksum(&s.as_bytes()[..CPF_BODY_LEN]) == s.as_bytes()[CPF_BODY_LEN..]
+}
+
+/// Whether `s` is a 14-character CNPJ, bare or punctuated, with good
+/// check digits.  Like [`cpf_valid`], rejects numbers with all digits
+/// the same.
+pub fn cnpj_valid(s: &str) -> bool {
+    let s = strip_punctuation(s);
+    let bytes = s.as_bytes();
+    s.len() == CNPJ_BODY_LEN + 2
+        && bytes[..CNPJ_BODY_LEN]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        && bytes[CNPJ_BODY_LEN..].iter().all(u8::is_ascii_digit)
+        && !all_same(&s)
+        && cnpj_checksum(&bytes[..CNPJ_BODY_LEN]) == bytes[CNPJ_BODY_LEN..]
+}
+
+/// Computes both check digits, where `weight(i, len)` is the weight of
+/// the `i`th of `len` characters.
+fn check_digits(body: &[u8], weight: impl Fn(usize, usize) -> u32) -> [u8; 2] {
+    let check_digit = |chars: &[u8]| {
+        let sum: u32 = chars
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| u32::from(c - b'0') * weight(i, chars.len()))
+            .sum();
+        match sum % 11 {
+            0 | 1 => b'0',
+            r => (11 - r) as u8 + b'0',
+        }
+    };
+    let first = check_digit(body);
+    let mut chars = [0; CNPJ_BODY_LEN + 1];
+    chars[..body.len()].copy_from_slice(body);
+    chars[body.len()] = first;
+    [first, check_digit(&chars[..=body.len()])]
+}
+
+/// Removes the dots, hyphen and slash of the punctuated forms.
+fn strip_punctuation(s: &str) -> SmallString {
+    s.chars()
+        .filter(|c| !matches!(c, '.' | '-' | '/'))
+        .collect()
+}
+
+fn all_same(s: &str) -> bool {
+    s.bytes().all(|b| b == s.as_bytes()[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_cpfs() {
+        assert_eq!(&cpf_checksum(b"123456789"), b"09");
+        assert!(cpf_valid("123.456.789-09"));
+        assert!(cpf_valid("12345678909"));
+        assert!(!cpf_valid("123.456.789-08"));
+        assert!(!cpf_valid("123.456.789-0"));
+        assert!(!cpf_valid("111.111.111-11"));
+        assert!(!cpf_valid("123.456.789-0x"));
+    }
+
+    #[test]
+    fn validates_cnpjs() {
+        assert_eq!(&cnpj_checksum(b"112223330001"), b"81");
+        assert!(cnpj_valid("11.222.333/0001-81"));
+        assert!(cnpj_valid("11222333000181"));
+        assert!(!cnpj_valid("11.222.333/0001-80"));
+        assert!(!cnpj_valid("00.000.000/0000-00"));
+        assert!(!cnpj_valid("11.222.333/0001-8"));
+    }
+
+    #[test]
+    fn validates_alphanumeric_cnpjs() {
+        assert_eq!(&cnpj_checksum(b"12ABC34501DE"), b"35");
+        assert!(cnpj_valid("12.ABC.345/01DE-35"));
+        assert!(!cnpj_valid("12.abc.345/01de-35"));
+        assert!(!cnpj_valid("12.ABC.345/01DE-3A"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn cpf_checksum_needs_nine_digits() {
+        cpf_checksum(b"12345678");
+    }
+}
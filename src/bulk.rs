@@ -0,0 +1,136 @@
+//! Validating many numbers packed into one buffer.
+
+use std::ops::Range;
+
+/// Luhn's doubled digits, with the digits of the product summed.
+const DOUBLED: [u32; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
+
+/// Validates each `buffer[offsets[i]]` into `out[i]`, for columnar data
+/// which keeps its strings in one arena.
+///
+/// As with [`Pan::new`](crate::Pan::new) and the other validating APIs, a
+/// value must be non-empty ASCII digits ending in a good check digit.
+/// The bytes needn't be UTF-8.  Panics if `out` and `offsets` differ in
+/// length, or if an offset is out of bounds.
+///
+/// ```
+/// let buffer = b"79927398713411111111111111112";
+/// let offsets = [0..11, 11..27, 27..29, 29..29];
+/// let mut out = [false; 4];
+/// luhn::valid_many(buffer, &offsets, &mut out);
+/// assert_eq!(out, [true, true, false, false]);
+/// ```
+pub fn valid_many(buffer: &[u8], offsets: &[Range<usize>], out: &mut [bool]) {
+    assert_eq!(
+        offsets.len(),
+        out.len(),
+        "Offsets and output differ in length"
+    );
+    for (range, out) in offsets.iter().zip(out.iter_mut()) {
+        *out = valid_bytes(&buffer[range.clone()]);
+    }
+}
+
+/// Validates each `width`-byte record of `data`, such as a dump of
+/// 16-digit PANs, one per 17-byte line.
+///
+/// Whitespace around each record, such as its newline or space padding,
+/// is ignored.  A shorter final record, eg. without a trailing newline, is
+/// validated too.  Panics if `width` is zero.
+///
+/// ```
+/// let data = b"4111111111111111\n4111111111111112\n79927398713     \n";
+/// assert_eq!(luhn::valid_fixed_records(data, 17), [true, false, true]);
+/// ```
+pub fn valid_fixed_records(data: &[u8], width: usize) -> Vec<bool> {
+    assert!(width > 0, "Records must be at least one byte wide");
+    data.chunks(width)
+        .map(|record| valid_bytes(trim_ascii(record)))
+        .collect()
+}
+
+/// Like `<[u8]>::trim_ascii`, which needs Rust 1.80.
+fn trim_ascii(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+/// Validates `digits` without branching on their values, so the loop can
+/// be vectorised: a bad character is noted rather than returned early.
+fn valid_bytes(digits: &[u8]) -> bool {
+    let mut sum = 0;
+    let mut bad = false;
+    for (i, &b) in digits.iter().rev().enumerate() {
+        let d = b.wrapping_sub(b'0');
+        bad |= d > 9;
+        // Clamped so indexing can't fail, which would be another branch.
+        let d = usize::from(d.min(9));
+        sum += if i % 2 == 1 { DOUBLED[d] } else { d as u32 };
+    }
+    !digits.is_empty() && !bad && sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agrees_with_check_digits() {
+        let values = [
+            "79927398713",
+            "79927398710",
+            "4111111111111111",
+            "0",
+            "00",
+            "18",
+            "",
+            "7992739871a",
+            "7992739871:",
+            "7992739871/",
+            "\u{e9}",
+        ];
+        let buffer = values.concat();
+        let mut offsets = Vec::new();
+        let mut start = 0;
+        for value in values.iter() {
+            offsets.push(start..start + value.len());
+            start += value.len();
+        }
+        let mut out = vec![false; values.len()];
+        valid_many(buffer.as_bytes(), &offsets, &mut out);
+        for (value, &valid) in values.iter().zip(&out) {
+            assert_eq!(valid, crate::check_digits(value).is_ok(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn ranges_may_overlap() {
+        let mut out = [false; 2];
+        valid_many(b"79927398713", &[0..11, 9..11], &mut out);
+        assert_eq!(out, [true, false]);
+    }
+
+    #[test]
+    fn validates_fixed_records() {
+        let data = b"4111111111111111\r\n     79927398713\r\n4111 11111111111\r\n";
+        assert_eq!(valid_fixed_records(data, 18), [true, true, false]);
+        // No newline at the end.
+        assert_eq!(valid_fixed_records(b"18\n18\n19", 3), [true, true, false]);
+        assert_eq!(valid_fixed_records(b"", 3), [false; 0]);
+        // A blank record has no digits.
+        assert_eq!(valid_fixed_records(b"18\n  \n", 3), [true, false]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn output_must_fit() {
+        valid_many(b"18", &[0..2, 0..1], &mut [false]);
+    }
+}
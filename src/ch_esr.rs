@@ -0,0 +1,68 @@
+//! Swiss payment references: ESR/ISR slips and QR-bill QR-references.
+//!
+//! The check digit is computed with the "recursive mod 10" scheme: each
+//! digit is added to a carry, which is then replaced by looking it up in
+//! a table.  Unlike Luhn, it catches every transposition of adjacent
+//! digits.
+//!
+//! ```
+//! use luhn::ch_esr;
+//!
+//! assert!(ch_esr::valid("21 00000 00003 13947 14300 09017"));
+//! assert_eq!(ch_esr::checksum(b"21000000000313947143000901"), b'7');
+//! ```
+
+use crate::small::SmallString;
+
+const TABLE: [u8; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+
+/// The lengths of references: 16 digits on older slips, and 27 on QR-bills
+/// and newer slips.
+const LENS: [usize; 2] = [16, 27];
+
+/// Computes the check digit for `body`.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    let carry = body.iter().fold(0, |carry, &b| {
+        assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+        TABLE[usize::from((carry + b - b'0') % 10)]
+    });
+    (10 - carry) % 10 + b'0'
+}
+
+/// Whether `s` is a 16 or 27-digit reference, optionally grouped with
+/// spaces, with a good check digit.
+pub fn valid(s: &str) -> bool {
+    let digits: SmallString = s.chars().filter(|&c| c != ' ').collect();
+    let bytes = digits.as_bytes();
+    LENS.contains(&bytes.len())
+        && bytes.iter().all(u8::is_ascii_digit)
+        && checksum(&bytes[..bytes.len() - 1]) == bytes[bytes.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_references() {
+        assert!(valid("210000000003139471430009017"));
+        assert!(!valid("210000000003139471430009018"));
+        assert!(valid("1234567890123456"));
+        assert!(!valid("12345678901234566"));
+        assert!(!valid("21000000000313947143000901a"));
+    }
+
+    #[test]
+    fn catches_adjacent_transpositions() {
+        let good = *b"210000000003139471430009017";
+        for i in 0..good.len() - 2 {
+            if good[i] != good[i + 1] {
+                let mut bad = good;
+                bad.swap(i, i + 1);
+                assert!(!valid(std::str::from_utf8(&bad).unwrap()), "{}", i);
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Folding characters that people and OCR mistake for digits.
+
+use std::borrow::Cow;
+
+use crate::small::SmallString;
+
+/// Which look-alike letters [`fold`](Confusables::fold) turns into the
+/// digits they were probably meant to be.
+///
+/// The default folds the letters which are hardly ever meant as letters
+/// in a number, `O`, `I` and `l`, but not `B`, which is also a common
+/// misreading of `8` but sometimes appears in real identifiers.
+///
+/// ```
+/// use luhn::Confusables;
+///
+/// let read_over_the_phone = "4III IIII IIII IIII";
+/// assert!(!luhn::valid(read_over_the_phone));
+/// assert!(Confusables::default().valid(read_over_the_phone));
+/// assert_eq!(Confusables::default().fold("79927398713"), "79927398713");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Confusables {
+    /// `O` and `o` for `0`.
+    pub letter_o: bool,
+    /// `I` for `1`.
+    pub letter_i: bool,
+    /// `l` for `1`.
+    pub letter_l: bool,
+    /// `B` for `8`.
+    pub letter_b: bool,
+}
+
+impl Confusables {
+    /// Folds nothing.
+    pub const NONE: Confusables = Confusables {
+        letter_o: false,
+        letter_i: false,
+        letter_l: false,
+        letter_b: false,
+    };
+
+    /// Everything folded, `B` included.
+    pub const ALL: Confusables = Confusables {
+        letter_o: true,
+        letter_i: true,
+        letter_l: true,
+        letter_b: true,
+    };
+
+    /// Replaces the chosen look-alikes in `s` with digits.
+    ///
+    /// Each replacement is one ASCII character for another, so byte
+    /// offsets into the result, such as those in a
+    /// [`LuhnError`](crate::LuhnError), are offsets into `s` too.
+    pub fn fold<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if !s.chars().any(|c| self.digit_for(c).is_some()) {
+            return Cow::Borrowed(s);
+        }
+        Cow::Owned(s.chars().map(|c| self.digit_for(c).unwrap_or(c)).collect())
+    }
+
+    /// Whether `s` is valid once folded, ignoring spaces and hyphens as
+    /// [`Pan::new`](crate::Pan::new) does.
+    pub fn valid(&self, s: &str) -> bool {
+        let folded: SmallString = s
+            .trim()
+            .chars()
+            .filter(|&c| c != ' ' && c != '-')
+            .map(|c| self.digit_for(c).unwrap_or(c))
+            .collect();
+        crate::check_digits(&folded).is_ok()
+    }
+
+    fn digit_for(&self, c: char) -> Option<char> {
+        match c {
+            'O' | 'o' if self.letter_o => Some('0'),
+            'I' if self.letter_i => Some('1'),
+            'l' if self.letter_l => Some('1'),
+            'B' if self.letter_b => Some('8'),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Confusables {
+    fn default() -> Self {
+        Confusables {
+            letter_o: true,
+            letter_i: true,
+            letter_l: true,
+            letter_b: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_what_it_is_told_to() {
+        let s = "4O12 888B 888l 88I2";
+        assert_eq!(Confusables::NONE.fold(s), s);
+        assert_eq!(Confusables::default().fold(s), "4012 888B 8881 8812");
+        assert_eq!(Confusables::ALL.fold(s), "4012 8888 8881 8812");
+        let only_l = Confusables {
+            letter_l: true,
+            ..Confusables::NONE
+        };
+        assert_eq!(only_l.fold("lIl"), "1I1");
+        assert!(matches!(Confusables::ALL.fold("4012"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn validates_folded_input() {
+        assert!(Confusables::ALL.valid("4O12 888B 8888 1881"));
+        assert!(!Confusables::default().valid("4O12 888B 8888 1881"));
+        assert!(Confusables::default().valid("799273987l3"));
+        assert!(!Confusables::default().valid("799273987L3"));
+        assert!(!Confusables::default().valid(""));
+    }
+}
@@ -0,0 +1,160 @@
+//! Validating a column of a CSV file.
+
+use std::io::{self, Read, Write};
+
+/// Counts from [`validate_csv_column`] or [`filter_csv_column`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CsvReport {
+    /// The number of records read, not counting the header.
+    pub rows: u64,
+    /// The number of records whose column was valid.
+    pub valid: u64,
+    /// The number of records whose column wasn't.
+    pub invalid: u64,
+}
+
+/// Copies a CSV file from `reader` to `writer`, adding a column saying
+/// whether each record's `column` is valid.
+///
+/// The input must have a header row.  The new column is called
+/// `<column>_valid` and holds `true` or `false`.  Values must be all
+/// ASCII digits, ending in a good check digit, to be valid.
+///
+/// ```
+/// let input = "id,card\n1,4111111111111111\n2,4111111111111112\n";
+/// let mut output = Vec::new();
+/// let report = luhn::validate_csv_column(input.as_bytes(), "card", &mut output).unwrap();
+/// assert_eq!(report.invalid, 1);
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "id,card,card_valid\n1,4111111111111111,true\n2,4111111111111112,false\n"
+/// );
+/// ```
+pub fn validate_csv_column<R: Read, W: Write>(
+    reader: R,
+    column: &str,
+    writer: W,
+) -> Result<CsvReport, csv::Error> {
+    process(reader, column, writer, false)
+}
+
+/// Copies a CSV file from `reader` to `writer`, keeping only the records
+/// whose `column` is valid.
+///
+/// The header row is kept.  Validity is decided as for
+/// [`validate_csv_column`].
+pub fn filter_csv_column<R: Read, W: Write>(
+    reader: R,
+    column: &str,
+    writer: W,
+) -> Result<CsvReport, csv::Error> {
+    process(reader, column, writer, true)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(reader, writer))
+)]
+fn process<R: Read, W: Write>(
+    reader: R,
+    column: &str,
+    writer: W,
+    filter: bool,
+) -> Result<CsvReport, csv::Error> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let mut writer = csv::Writer::from_writer(writer);
+
+    let mut headers = reader.byte_headers()?.clone();
+    let index = headers
+        .iter()
+        .position(|h| h == column.as_bytes())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no column named {:?}", column),
+            )
+        })?;
+    if !filter {
+        headers.push_field(format!("{}_valid", column).as_bytes());
+    }
+    writer.write_byte_record(&headers)?;
+
+    let mut report = CsvReport::default();
+    let mut record = csv::ByteRecord::new();
+    while reader.read_byte_record(&mut record)? {
+        report.rows += 1;
+        let valid = record
+            .get(index)
+            .and_then(|field| std::str::from_utf8(field).ok())
+            .is_some_and(|field| crate::check_digits(field).is_ok());
+        if valid {
+            report.valid += 1;
+        } else {
+            report.invalid += 1;
+        }
+        if !filter {
+            record.push_field(if valid { b"true" } else { b"false" });
+            writer.write_byte_record(&record)?;
+        } else if valid {
+            writer.write_byte_record(&record)?;
+        }
+    }
+    writer.flush()?;
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        rows = report.rows,
+        valid = report.valid,
+        invalid = report.invalid,
+        "validated column"
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "name,card\n\
+                         alice,4111111111111111\n\
+                         bob,\n\
+                         \"carol, jr\",79927398713\n\
+                         dave,7992739871x\n";
+
+    #[test]
+    fn filters_invalid_rows() {
+        let mut output = Vec::new();
+        let report = filter_csv_column(INPUT.as_bytes(), "card", &mut output).unwrap();
+        assert_eq!(
+            report,
+            CsvReport {
+                rows: 4,
+                valid: 2,
+                invalid: 2
+            }
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "name,card\nalice,4111111111111111\n\"carol, jr\",79927398713\n"
+        );
+    }
+
+    #[test]
+    fn appends_a_column() {
+        let mut output = Vec::new();
+        validate_csv_column(INPUT.as_bytes(), "card", &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "name,card,card_valid\n\
+             alice,4111111111111111,true\n\
+             bob,,false\n\
+             \"carol, jr\",79927398713,true\n\
+             dave,7992739871x,false\n"
+        );
+    }
+
+    #[test]
+    fn missing_column() {
+        let err = validate_csv_column(INPUT.as_bytes(), "pan", io::sink()).unwrap_err();
+        assert_eq!(err.to_string(), "no column named \"pan\"");
+    }
+}
@@ -0,0 +1,58 @@
+//! The Damm check digit, which like [Verhoeff](crate::verhoeff) catches
+//! all single-digit errors and adjacent transpositions, but with a single
+//! table.
+//!
+//! ```
+//! use luhn::damm;
+//!
+//! assert_eq!(damm::checksum(b"572"), b'4');
+//! assert!(damm::valid("5724"));
+//! ```
+
+use crate::check_digits_with;
+
+/// A totally anti-symmetric quasigroup of order 10, with zeros on the
+/// diagonal.
+const TABLE: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+/// Computes the Damm check digit for `body`.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    body.iter().fold(0, |interim, &b| {
+        assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+        TABLE[usize::from(interim)][usize::from(b - b'0')]
+    }) + b'0'
+}
+
+/// Whether `s` is ASCII digits ending in a good Damm check digit.
+pub fn valid(s: &str) -> bool {
+    check_digits_with(s, checksum).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_check_digits() {
+        assert_eq!(checksum(b"572"), b'4');
+        assert_eq!(checksum(b"12345"), b'9');
+        assert_eq!(checksum(b""), b'0');
+        assert!(valid("5724"));
+        assert!(!valid("5723"));
+        assert!(!valid(""));
+        assert!(!valid("572a"));
+    }
+}
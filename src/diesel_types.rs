@@ -0,0 +1,38 @@
+//! Diesel `ToSql`/`FromSql` implementations for the newtypes.
+//!
+//! They're stored as `Text`, and validated when loaded.  The round-trip
+//! tests are in `db-tests`, to keep SQLite out of the dev-dependencies.
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::{Imei, Isin, Pan};
+
+macro_rules! impl_diesel {
+    ($($t:ty),*) => {$(
+        impl<DB> FromSql<Text, DB> for $t
+        where
+            DB: Backend,
+            String: FromSql<Text, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                let s = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+                Ok(<$t>::new(&s)?)
+            }
+        }
+
+        impl<DB> ToSql<Text, DB> for $t
+        where
+            DB: Backend,
+            str: ToSql<Text, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                <str as ToSql<Text, DB>>::to_sql(self.as_str(), out)
+            }
+        }
+    )*};
+}
+
+impl_diesel!(Pan, Isin, Imei);
@@ -0,0 +1,94 @@
+//! The error type shared by the validating APIs.
+
+use std::error::Error;
+use std::fmt;
+
+/// The reasons an identifier can fail validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
+#[non_exhaustive]
+pub enum LuhnError {
+    /// The character at byte offset `position` isn't allowed there.
+    InvalidCharacter { position: usize, character: char },
+    /// The input has `len` characters, but at least `min` are required.
+    TooShort { len: usize, min: usize },
+    /// The input has `len` characters, but at most `max` are allowed.
+    TooLong { len: usize, max: usize },
+    /// The check digit at byte offset `position` is `found`, but the rest
+    /// of the input calls for `expected`.
+    BadCheckDigit {
+        position: usize,
+        expected: char,
+        found: char,
+    },
+}
+
+impl LuhnError {
+    /// The kind of error this is, without the details.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            LuhnError::InvalidCharacter { .. } => ErrorKind::InvalidCharacter,
+            LuhnError::TooShort { .. } => ErrorKind::TooShort,
+            LuhnError::TooLong { .. } => ErrorKind::TooLong,
+            LuhnError::BadCheckDigit { .. } => ErrorKind::BadCheckDigit,
+        }
+    }
+}
+
+/// The variants of [`LuhnError`], without their details, for counting
+/// failures by reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    InvalidCharacter,
+    TooShort,
+    TooLong,
+    BadCheckDigit,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::InvalidCharacter => "invalid character",
+            ErrorKind::TooShort => "too short",
+            ErrorKind::TooLong => "too long",
+            ErrorKind::BadCheckDigit => "bad check digit",
+        })
+    }
+}
+
+impl fmt::Display for LuhnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LuhnError::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "invalid character {:?} at position {}",
+                character, position
+            ),
+            LuhnError::TooShort { len, min } => {
+                write!(
+                    f,
+                    "too short: {} characters, expected at least {}",
+                    len, min
+                )
+            }
+            LuhnError::TooLong { len, max } => {
+                write!(f, "too long: {} characters, expected at most {}", len, max)
+            }
+            LuhnError::BadCheckDigit {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "bad check digit {:?} at position {}, expected {:?}",
+                found, position, expected
+            ),
+        }
+    }
+}
+
+impl Error for LuhnError {}
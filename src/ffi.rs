@@ -0,0 +1,127 @@
+//! A C interface, for linking from C and C++.
+//!
+//! The crate is only built as a Rust library by default, so ask for a
+//! shared library to link against explicitly:
+//!
+//! ```sh
+//! cargo rustc --release --lib --crate-type cdylib --features ffi
+//! ```
+//!
+//! This produces eg. `target/release/libluhn.so`.  Generate a header with
+//! `cbindgen --crate luhn --output luhn.h`.
+//!
+//! Every function returns one of the `LUHN_*` status codes below.  Their
+//! values are stable.  Strings are passed as a pointer and a length, and
+//! needn't be NUL-terminated.
+
+use std::os::raw::{c_char, c_int};
+use std::slice;
+
+use crate::{check_digits, LuhnError};
+
+/// The input validated, or the operation succeeded.
+pub const LUHN_OK: c_int = 0;
+/// The input is well-formed, but its check digit is wrong.
+pub const LUHN_INVALID: c_int = 1;
+/// A required pointer was null.
+pub const LUHN_ERR_NULL_POINTER: c_int = -1;
+/// The input contains a character which isn't allowed.
+pub const LUHN_ERR_INVALID_CHARACTER: c_int = -2;
+/// The input is empty.
+pub const LUHN_ERR_EMPTY: c_int = -3;
+
+unsafe fn input<'a>(s: *const c_char, len: usize) -> Option<&'a [u8]> {
+    if s.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(s as *const u8, len))
+    }
+}
+
+/// Checks whether the `len` bytes at `s` are decimal digits which
+/// validate.
+///
+/// Returns `LUHN_OK` if they do and `LUHN_INVALID` if they don't.
+///
+/// # Safety
+///
+/// `s` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn luhn_valid(s: *const c_char, len: usize) -> c_int {
+    let s = match input(s, len) {
+        Some(s) => s,
+        None => return LUHN_ERR_NULL_POINTER,
+    };
+    // Non-ASCII input is caught as an invalid character below.
+    let s = String::from_utf8_lossy(s);
+    match check_digits(&s) {
+        Ok(()) => LUHN_OK,
+        Err(LuhnError::BadCheckDigit { .. }) => LUHN_INVALID,
+        Err(LuhnError::TooShort { .. }) => LUHN_ERR_EMPTY,
+        Err(_) => LUHN_ERR_INVALID_CHARACTER,
+    }
+}
+
+/// Computes the check digit for the `len` bytes at `s`, which must be
+/// uppercase alphanumeric ASCII, and stores it in `*out`.
+///
+/// # Safety
+///
+/// `s` must point to at least `len` readable bytes, and `out` must be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn luhn_checksum(s: *const c_char, len: usize, out: *mut c_char) -> c_int {
+    let s = match input(s, len) {
+        Some(s) => s,
+        None => return LUHN_ERR_NULL_POINTER,
+    };
+    if out.is_null() {
+        return LUHN_ERR_NULL_POINTER;
+    }
+    match crate::try_checksum(s) {
+        Ok(check) => {
+            *out = check as c_char;
+            LUHN_OK
+        }
+        Err(_) => LUHN_ERR_INVALID_CHARACTER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    fn valid(s: &str) -> c_int {
+        unsafe { luhn_valid(s.as_ptr() as *const c_char, s.len()) }
+    }
+
+    fn checksum(s: &str) -> Result<char, c_int> {
+        let mut out = 0;
+        match unsafe { luhn_checksum(s.as_ptr() as *const c_char, s.len(), &mut out) } {
+            LUHN_OK => Ok(out as u8 as char),
+            err => Err(err),
+        }
+    }
+
+    #[test]
+    fn validates() {
+        assert_eq!(valid("4111111111111111"), LUHN_OK);
+        assert_eq!(valid("4111111111111112"), LUHN_INVALID);
+        assert_eq!(valid("4111-1111"), LUHN_ERR_INVALID_CHARACTER);
+        assert_eq!(valid(""), LUHN_ERR_EMPTY);
+        assert_eq!(unsafe { luhn_valid(ptr::null(), 4) }, LUHN_ERR_NULL_POINTER);
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(checksum("7992739871"), Ok('3'));
+        assert_eq!(checksum("US037833100"), Ok('5'));
+        assert_eq!(checksum("us037833100"), Err(LUHN_ERR_INVALID_CHARACTER));
+        let s = "1234";
+        assert_eq!(
+            unsafe { luhn_checksum(s.as_ptr() as *const c_char, s.len(), ptr::null_mut()) },
+            LUHN_ERR_NULL_POINTER
+        );
+    }
+}
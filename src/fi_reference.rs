@@ -0,0 +1,107 @@
+//! Finnish bank reference numbers (viitenumero), which identify invoice
+//! payments.
+//!
+//! A reference is a base number of 3 to 19 digits and a check digit,
+//! weighting the digits 7, 3, 1, 7, 3, 1... from the right.  References
+//! are printed in groups of five digits, counting from the right, which
+//! validation accepts.  They can also be written in the international
+//! ISO 11649 form, `RF` and two [MOD 97-10](crate::iso7064::mod_97_10)
+//! check digits before the reference.
+//!
+//! ```
+//! use luhn::fi_reference;
+//!
+//! assert!(fi_reference::valid("1232"));
+//! assert!(fi_reference::valid("12 34561"));
+//! assert_eq!(fi_reference::checksum(b"123"), b'2');
+//! assert_eq!(fi_reference::to_rf("1232").as_deref(), Some("RF111232"));
+//! assert_eq!(fi_reference::from_rf("RF111232").as_deref(), Some("1232"));
+//! ```
+
+use crate::iso7064::mod_97_10;
+use crate::small::SmallString;
+
+const MIN_LEN: usize = 4;
+const MAX_LEN: usize = 20;
+
+/// Computes the check digit for a base number.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(base: &[u8]) -> u8 {
+    let sum: u32 = base
+        .iter()
+        .rev()
+        .zip([7, 3, 1].iter().cycle())
+        .map(|(&b, &weight)| {
+            assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+            u32::from(b - b'0') * weight
+        })
+        .sum();
+    (10 - sum % 10) as u8 % 10 + b'0'
+}
+
+/// Whether `s` is a reference of 4 to 20 digits, optionally grouped with
+/// spaces, with a good check digit.
+pub fn valid(s: &str) -> bool {
+    let digits: SmallString = s.chars().filter(|&c| c != ' ').collect();
+    let bytes = digits.as_bytes();
+    (MIN_LEN..=MAX_LEN).contains(&bytes.len())
+        && bytes.iter().all(u8::is_ascii_digit)
+        && checksum(&bytes[..bytes.len() - 1]) == bytes[bytes.len() - 1]
+}
+
+/// Converts a valid Finnish reference into the ISO 11649 form, without
+/// spaces.  `None` if `reference` isn't [`valid`].
+pub fn to_rf(reference: &str) -> Option<String> {
+    if !valid(reference) {
+        return None;
+    }
+    let digits = reference.replace(' ', "");
+    let mut rearranged = digits.clone().into_bytes();
+    rearranged.extend_from_slice(b"RF");
+    let [a, b] = mod_97_10::checksum(&rearranged);
+    Some(format!("RF{}{}{}", a as char, b as char, digits))
+}
+
+/// Converts an ISO 11649 reference, optionally grouped with spaces, back
+/// into the Finnish form.  `None` unless both its RF check digits and the
+/// Finnish reference inside are good.
+pub fn from_rf(rf: &str) -> Option<String> {
+    let rf = rf.replace(' ', "");
+    let (head, reference) = (rf.get(..4)?, rf.get(4..)?);
+    if !head.starts_with("RF") || !valid(reference) {
+        return None;
+    }
+    mod_97_10::valid(&format!("{}{}", reference, head)).then(|| reference.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_references() {
+        assert_eq!(checksum(b"1234561"), b'4');
+        assert!(valid("12345614"));
+        assert!(valid("123 45614"));
+        assert!(!valid("12345615"));
+        assert!(!valid("123"));
+        assert!(!valid(&"1".repeat(21)));
+        assert!(!valid("1234561a"));
+    }
+
+    #[test]
+    fn converts_to_and_from_rf() {
+        for reference in ["1232", "12345614", "1 23456 78901 23450"] {
+            let rf = to_rf(reference).unwrap();
+            assert!(rf.starts_with("RF"));
+            assert_eq!(from_rf(&rf), Some(reference.replace(' ', "")));
+        }
+        assert_eq!(to_rf("12345614").as_deref(), Some("RF0212345614"));
+        assert_eq!(from_rf("RF02 1234 5614").as_deref(), Some("12345614"));
+        assert_eq!(from_rf("RF03 1234 5614"), None);
+        assert_eq!(from_rf("RF18539007547034"), None);
+        assert_eq!(to_rf("12345615"), None);
+        assert_eq!(from_rf("RF5"), None);
+    }
+}
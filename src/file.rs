@@ -0,0 +1,281 @@
+//! Validating whole files through a memory map.
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::scan::{scan_monitored, Match, ScanConfig};
+use crate::{validate_lines_monitored, LineReport, Monitor};
+
+/// What [`validate_file`] does with a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileConfig {
+    /// Validates each line, as [`validate_lines`](crate::validate_lines)
+    /// does.
+    Lines,
+    /// Looks for numbers in the text, as
+    /// [`find_candidates_in_bytes`](crate::scan::find_candidates_in_bytes)
+    /// does.
+    Scan(ScanConfig),
+}
+
+/// What [`validate_file`] found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileReport {
+    /// The size of the file.
+    pub bytes: u64,
+    /// For [`FileConfig::Lines`], how the lines fared; empty otherwise.
+    pub lines: LineReport,
+    /// For [`FileConfig::Scan`], the numbers found; empty otherwise.
+    pub matches: Vec<Match>,
+    /// Whether the job was cancelled through a [`Monitor`], so that the
+    /// report only covers the start of the file.
+    pub cancelled: bool,
+}
+
+/// Validates the file at `path`, memory-mapping it rather than reading
+/// it, which saves copying very large files.
+///
+/// The file mustn't be modified while it's being validated: the map would
+/// change underneath the validator, and truncating the file may crash the
+/// process.
+///
+/// ```no_run
+/// use luhn::scan::ScanConfig;
+/// use luhn::FileConfig;
+///
+/// let report = luhn::validate_file("export.csv", &FileConfig::Scan(ScanConfig::default()))?;
+/// println!("{} card numbers in {} bytes", report.matches.len(), report.bytes);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn validate_file(path: impl AsRef<Path>, config: &FileConfig) -> io::Result<FileReport> {
+    validate_file_monitored(path, config, &Monitor::new())
+}
+
+/// Like [`validate_file`], but tells `monitor` how it's getting on.
+///
+/// ```
+/// use luhn::{FileConfig, Monitor, Progress};
+/// use std::sync::Mutex;
+///
+/// let path = std::env::temp_dir().join("luhn-validate-file-monitored.txt");
+/// std::fs::write(&path, "4111111111111111\n4111111111111112\n")?;
+/// let last = Mutex::new(Progress::default());
+/// let record = |progress| *last.lock().unwrap() = progress;
+/// let monitor = Monitor::new().on_progress(&record);
+/// let report = luhn::validate_file_monitored(&path, &FileConfig::Lines, &monitor)?;
+/// assert_eq!(report.lines.invalid, 1);
+/// assert_eq!(*last.lock().unwrap(), Progress { bytes: 34, matches: 1 });
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn validate_file_monitored(
+    path: impl AsRef<Path>,
+    config: &FileConfig,
+    monitor: &Monitor,
+) -> io::Result<FileReport> {
+    let (mut report, map) = map(path.as_ref())?;
+    let bytes: &[u8] = map.as_deref().unwrap_or_default();
+    match config {
+        FileConfig::Lines => {
+            report.lines = validate_lines_monitored(bytes, monitor)?;
+            report.cancelled = report.lines.cancelled;
+        }
+        FileConfig::Scan(config) => {
+            let (matches, cancelled) = scan_monitored(bytes, config, monitor);
+            report.matches = matches;
+            report.cancelled = cancelled;
+        }
+    }
+    Ok(report)
+}
+
+/// Like [`validate_file`], but spreads the work over rayon's thread pool,
+/// as [`validate_lines_parallel`](crate::validate_lines_parallel) and
+/// [`find_candidates_parallel`](crate::scan::find_candidates_parallel)
+/// do.  The report is the same.
+#[cfg(feature = "parallel")]
+pub fn validate_file_parallel(
+    path: impl AsRef<Path>,
+    config: &FileConfig,
+) -> io::Result<FileReport> {
+    validate_file_parallel_monitored(path, config, &Monitor::new())
+}
+
+/// Like [`validate_file_parallel`], but tells `monitor` how it's getting
+/// on, from rayon's threads.
+///
+/// ```
+/// use luhn::scan::ScanConfig;
+/// use luhn::{FileConfig, Monitor, Progress};
+/// use std::sync::Mutex;
+///
+/// let path = std::env::temp_dir().join("luhn-validate-file-parallel-monitored.txt");
+/// std::fs::write(&path, "paid with 4111 1111 1111 1111 today")?;
+/// let last = Mutex::new(Progress::default());
+/// let record = |progress| *last.lock().unwrap() = progress;
+/// let monitor = Monitor::new().on_progress(&record);
+/// let config = FileConfig::Scan(ScanConfig::default());
+/// let report = luhn::validate_file_parallel_monitored(&path, &config, &monitor)?;
+/// assert_eq!(report.matches.len(), 1);
+/// assert_eq!(*last.lock().unwrap(), Progress { bytes: 35, matches: 1 });
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "parallel")]
+pub fn validate_file_parallel_monitored(
+    path: impl AsRef<Path>,
+    config: &FileConfig,
+    monitor: &Monitor,
+) -> io::Result<FileReport> {
+    let (mut report, map) = map(path.as_ref())?;
+    let bytes: &[u8] = map.as_deref().unwrap_or_default();
+    match config {
+        FileConfig::Lines => {
+            report.lines =
+                crate::lines::validate_lines_in_chunks(bytes, Monitor::INTERVAL, monitor);
+            report.cancelled = report.lines.cancelled;
+        }
+        FileConfig::Scan(config) => {
+            let (matches, cancelled) =
+                crate::scan::find_candidates_in_chunks(bytes, config, Monitor::INTERVAL, monitor);
+            report.matches = matches;
+            report.cancelled = cancelled;
+        }
+    }
+    Ok(report)
+}
+
+/// Maps the file at `path`, returning an empty report giving its size,
+/// and the map unless the file is empty.
+fn map(path: &Path) -> io::Result<(FileReport, Option<Mmap>)> {
+    let file = File::open(path)?;
+    let bytes = file.metadata()?.len();
+    let report = FileReport {
+        bytes,
+        ..FileReport::default()
+    };
+    // Empty files can't be mapped on every platform.
+    if bytes == 0 {
+        return Ok((report, None));
+    }
+    // SAFETY: the map is only read, and the docs ask that the file not be
+    // modified meanwhile.
+    let map = unsafe { Mmap::map(&file)? };
+    Ok((report, Some(map)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A file in the temporary directory, removed when dropped.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &[u8]) -> TempFile {
+            let path = std::env::temp_dir().join(format!("luhn-{}-{}", std::process::id(), name));
+            fs::write(&path, contents).unwrap();
+            TempFile(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn validates_lines() {
+        let file = TempFile::new("lines", b"4111111111111111\n4111111111111112\n");
+        let report = validate_file(&file.0, &FileConfig::Lines).unwrap();
+        assert_eq!(report.bytes, 34);
+        assert_eq!((report.lines.valid, report.lines.invalid), (1, 1));
+        assert!(report.matches.is_empty());
+    }
+
+    #[test]
+    fn scans_text() {
+        let file = TempFile::new("scan", b"paid with 4111 1111 1111 1111\n");
+        let report = validate_file(&file.0, &FileConfig::Scan(ScanConfig::default())).unwrap();
+        assert_eq!(report.matches.len(), 1);
+        assert_eq!(report.matches[0].range, 10..29);
+        assert_eq!(report.lines, LineReport::default());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_agrees() {
+        let file = TempFile::new("parallel", b"4111111111111111\nx 4111 1111 1111 1111\n");
+        for config in [FileConfig::Lines, FileConfig::Scan(ScanConfig::default())].iter() {
+            assert_eq!(
+                validate_file_parallel(&file.0, config).unwrap(),
+                validate_file(&file.0, config).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn reports_progress() {
+        use crate::Progress;
+        use std::sync::Mutex;
+
+        let file = TempFile::new("progress", b"4111111111111111\n4111111111111112\n");
+        let last = Mutex::new(None);
+        let record = |progress| *last.lock().unwrap() = Some(progress);
+        let monitor = Monitor::new().on_progress(&record);
+        validate_file_monitored(&file.0, &FileConfig::Lines, &monitor).unwrap();
+        let expected = Progress {
+            bytes: 34,
+            matches: 1,
+        };
+        assert_eq!(*last.lock().unwrap(), Some(expected));
+        #[cfg(feature = "parallel")]
+        {
+            *last.lock().unwrap() = None;
+            validate_file_parallel_monitored(&file.0, &FileConfig::Lines, &monitor).unwrap();
+            assert_eq!(*last.lock().unwrap(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        use crate::CancelToken;
+
+        let file = TempFile::new("cancel", b"4111111111111111\n");
+        let token = CancelToken::new();
+        token.cancel();
+        let monitor = Monitor::new().cancel_on(&token);
+        for config in [FileConfig::Lines, FileConfig::Scan(ScanConfig::default())].iter() {
+            let report = validate_file_monitored(&file.0, config, &monitor).unwrap();
+            assert!(report.cancelled);
+            assert_eq!((report.lines.lines, report.matches.len()), (0, 0));
+            #[cfg(feature = "parallel")]
+            assert!(
+                validate_file_parallel_monitored(&file.0, config, &monitor)
+                    .unwrap()
+                    .cancelled
+            );
+        }
+    }
+
+    #[test]
+    fn handles_empty_and_missing_files() {
+        let file = TempFile::new("empty", b"");
+        let report = validate_file(&file.0, &FileConfig::Lines).unwrap();
+        assert_eq!(report, FileReport::default());
+        let missing = std::env::temp_dir().join("luhn-no-such-file");
+        assert_eq!(
+            validate_file(missing, &FileConfig::Lines)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
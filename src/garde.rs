@@ -0,0 +1,66 @@
+//! Custom rules for the [garde](https://docs.rs/garde) crate.
+//!
+//! ```
+//! use garde::Validate;
+//!
+//! #[derive(Validate)]
+//! struct Payment {
+//!     #[garde(custom(luhn::garde::pan))]
+//!     card_number: String,
+//! }
+//!
+//! let payment = Payment {
+//!     card_number: "4111111111111112".to_string(),
+//! };
+//! let report = payment.validate().unwrap_err();
+//! assert_eq!(report.iter().next().unwrap().0.to_string(), "card_number");
+//! ```
+
+use ::garde::Error;
+
+use crate::{check_digits, Imei, Isin, Pan};
+
+/// Checks that `value` is a string of decimal digits which validates.
+pub fn valid<T: AsRef<str> + ?Sized, C>(value: &T, _context: &C) -> ::garde::Result {
+    check_digits(value.as_ref()).map_err(|e| Error::new(e.to_string()))
+}
+
+/// Checks that `value` is a valid card number of 12 to 19 digits.
+pub fn pan<T: AsRef<str> + ?Sized, C>(value: &T, _context: &C) -> ::garde::Result {
+    Pan::new(value.as_ref())
+        .map(drop)
+        .map_err(|e| Error::new(e.to_string()))
+}
+
+/// Checks that `value` is a valid 15-digit IMEI.
+pub fn imei<T: AsRef<str> + ?Sized, C>(value: &T, _context: &C) -> ::garde::Result {
+    Imei::new(value.as_ref())
+        .map(drop)
+        .map_err(|e| Error::new(e.to_string()))
+}
+
+/// Checks that `value` is a valid ISIN.
+pub fn isin<T: AsRef<str> + ?Sized, C>(value: &T, _context: &C) -> ::garde::Result {
+    Isin::new(value.as_ref())
+        .map(drop)
+        .map_err(|e| Error::new(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_failures() {
+        assert!(valid("18", &()).is_ok());
+        assert!(pan("18", &()).is_err());
+        assert!(pan(&"4111111111111111".to_string(), &()).is_ok());
+        assert!(imei("490154203237518", &()).is_ok());
+        assert!(isin("US0378331005", &()).is_ok());
+        let err = valid("4111111111111112", &()).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "bad check digit '2' at position 15, expected '1'"
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Random generation of Luhn-valid strings.
+
+use std::io::{self, Write};
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::pattern::{Pattern, PatternError, Slot};
+use crate::{checksum, imei, valid, CardNetwork, Imei, Pan};
+
+/// Documented test card prefixes for each network, with the card length.
+///
+/// These come from the test numbers published by the networks and the
+/// big payment processors, so cards generated from them shouldn't
+/// collide with real cardholders.
+fn test_bins(network: CardNetwork) -> &'static [(&'static str, usize)] {
+    match network {
+        CardNetwork::Visa => &[("411111", 16), ("424242", 16), ("401288", 16)],
+        CardNetwork::Mastercard => &[("555555", 16), ("510510", 16), ("222300", 16)],
+        CardNetwork::AmericanExpress => &[("378282", 15), ("371449", 15)],
+        CardNetwork::Discover => &[("601111", 16), ("601100", 16)],
+        CardNetwork::DinersClub => &[("305693", 14), ("385200", 14)],
+        CardNetwork::Jcb => &[("353011", 16), ("356600", 16)],
+        CardNetwork::UnionPay => &[("620000", 16)],
+    }
+}
+
+/// Generates a random string of `len` decimal digits which validates.
+///
+/// The first `len - 1` digits are chosen uniformly at random and the
+/// last one is the Luhn check digit.  Panics if `len` is zero.
+pub fn generate<R: RngCore + ?Sized>(len: usize, rng: &mut R) -> String {
+    generate_with_prefix("", len, rng)
+}
+
+/// Generates a random valid string of `total_len` digits starting with
+/// `prefix`.
+///
+/// Useful for minting numbers in a particular BIN range, eg. `"411111"`
+/// for test Visa cards.  The digits between the prefix and the check
+/// digit are chosen uniformly at random.  Panics if `prefix` contains
+/// anything other than ASCII digits, or if it leaves no room for the
+/// check digit.
+pub fn generate_with_prefix<R: RngCore + ?Sized>(
+    prefix: &str,
+    total_len: usize,
+    rng: &mut R,
+) -> String {
+    let mut digits = Vec::with_capacity(total_len);
+    push_with_prefix(&mut digits, prefix, total_len, rng);
+    String::from_utf8(digits).unwrap()
+}
+
+/// Appends a random valid number to `buf`; see `generate_with_prefix`.
+fn push_with_prefix<R: RngCore + ?Sized>(
+    buf: &mut Vec<u8>,
+    prefix: &str,
+    total_len: usize,
+    rng: &mut R,
+) {
+    assert!(
+        prefix.bytes().all(|b| b.is_ascii_digit()),
+        "Not a decimal prefix: {:?}",
+        prefix
+    );
+    assert!(
+        prefix.len() < total_len,
+        "No room for a check digit after {:?} in {} digits",
+        prefix,
+        total_len
+    );
+    let start = buf.len();
+    buf.extend_from_slice(prefix.as_bytes());
+    buf.extend((prefix.len() + 1..total_len).map(|_| b'0' + rng.gen_range(0..10)));
+    let digit = checksum(&buf[start..]);
+    buf.push(digit);
+}
+
+/// What [`generate_many`] should generate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateConfig<'a> {
+    /// The digits every number starts with.
+    pub prefix: &'a str,
+    /// The length of each number, including the prefix and check digit.
+    pub len: usize,
+}
+
+impl Default for GenerateConfig<'_> {
+    fn default() -> Self {
+        GenerateConfig {
+            prefix: "",
+            len: 16,
+        }
+    }
+}
+
+/// Writes `count` random valid numbers to `out`, one per line.
+///
+/// Nothing is collected in memory, so this is suitable for producing
+/// millions of fixtures.  `out` isn't buffered here; wrap it in a
+/// `BufWriter` if it's a file or socket.  Panics under the same
+/// conditions as [`generate_with_prefix`].
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(count, len = config.len, prefix_len = config.prefix.len())
+    )
+)]
+pub fn generate_many<R: RngCore + ?Sized>(
+    count: usize,
+    config: &GenerateConfig,
+    rng: &mut R,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut line = Vec::with_capacity(config.len + 1);
+    for _ in 0..count {
+        line.clear();
+        push_with_prefix(&mut line, config.prefix, config.len, rng);
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    Ok(())
+}
+
+/// Generates a valid string of `len` digits determined entirely by `seed`.
+///
+/// The same seed gives the same string on every run and every machine,
+/// which makes this suitable for snapshot tests and fuzz corpora.  To
+/// reproduce a whole sequence of numbers, pass a seeded
+/// `rand_chacha::ChaCha8Rng` to the other generators instead.
+pub fn generate_seeded(seed: u64, len: usize) -> String {
+    generate(len, &mut ChaCha8Rng::seed_from_u64(seed))
+}
+
+/// Generates a random card number for the given network.
+///
+/// Only documented test BINs are used, so the result is safe to use in
+/// CI and demos.
+pub fn generate_test_card<R: RngCore + ?Sized>(network: CardNetwork, rng: &mut R) -> Pan {
+    let bins = test_bins(network);
+    let (bin, len) = bins[rng.gen_range(0..bins.len())];
+    #[cfg(feature = "tracing")]
+    tracing::trace!(?network, bin, len, "generating test card");
+    Pan::new(&generate_with_prefix(bin, len, rng)).unwrap()
+}
+
+/// Generates `count` random IMEIs, each drawn from one of `tacs`.
+///
+/// The TAC is picked uniformly from `tacs` and the six-digit serial number
+/// is random, so the IMEIs are plausible for the given device models.
+/// Panics if `tacs` is empty or any of them isn't eight ASCII digits.
+pub fn generate_imeis<'a, R: RngCore + ?Sized>(
+    tacs: &'a [&'a str],
+    count: usize,
+    rng: &'a mut R,
+) -> impl Iterator<Item = Imei> + 'a {
+    assert!(!tacs.is_empty(), "No TACs to generate IMEIs from");
+    for tac in tacs {
+        assert!(
+            tac.len() == 8 && tac.bytes().all(|b| b.is_ascii_digit()),
+            "Not a TAC: {:?}",
+            tac
+        );
+    }
+    (0..count).map(move |_| {
+        let tac = tacs[rng.gen_range(0..tacs.len())];
+        Imei::new(&generate_with_prefix(tac, imei::LEN, rng)).unwrap()
+    })
+}
+
+/// De-identifies a card number while keeping it realistic.
+///
+/// The first six and last four digits are kept and the digits in between
+/// are randomised, except for one which is chosen so that the result
+/// still validates.  Panics if `pan` isn't all ASCII digits, or is too
+/// short to have a middle (fewer than 11 digits).
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip_all, fields(len = pan.len()))
+)]
+pub fn mask_preserving_luhn<R: RngCore + ?Sized>(pan: &str, rng: &mut R) -> String {
+    assert!(
+        pan.bytes().all(|b| b.is_ascii_digit()),
+        "Not a card number: {:?}",
+        pan
+    );
+    assert!(pan.len() >= 11, "Too short to mask: {:?}", pan);
+    let mut digits = pan.as_bytes().to_vec();
+    let end = digits.len() - 4;
+    for d in &mut digits[6..end - 1] {
+        *d = b'0' + rng.gen_range(0..10);
+    }
+    // Exactly one digit in any position makes the number validate.
+    for d in b'0'..=b'9' {
+        digits[end - 1] = d;
+        if valid(std::str::from_utf8(&digits).unwrap()) {
+            break;
+        }
+    }
+    String::from_utf8(digits).unwrap()
+}
+
+/// Generates a random string which matches `template`, a
+/// [`Pattern`](crate::pattern::Pattern), each `#` a random digit and each
+/// `?` a random digit or uppercase letter.
+///
+/// As when matching, the check digit covers all the digits and uppercase
+/// letters in the result, whether random or written in the template, with
+/// letters counting as two digits as in [`checksum`].
+///
+/// ```
+/// use luhn::Isin;
+///
+/// let mut rng = rand::thread_rng();
+/// let card = luhn::generate_from_template("4111-####-####-###C", &mut rng).unwrap();
+/// assert!(luhn::valid(&card.replace('-', "")));
+///
+/// let isin = luhn::generate_from_template("US?????????C", &mut rng).unwrap();
+/// assert!(Isin::new(&isin).is_ok());
+/// ```
+pub fn generate_from_template<R: RngCore + ?Sized>(
+    template: &str,
+    rng: &mut R,
+) -> Result<String, PatternError> {
+    const ALPHANUMERIC: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let pattern = Pattern::new(template)?;
+    let mut out = Vec::with_capacity(template.len());
+    let mut check = 0;
+    for &slot in pattern.slots() {
+        let c = match slot {
+            Slot::Digit => char::from(b'0' + rng.gen_range(0..10)),
+            Slot::Alphanumeric => char::from(ALPHANUMERIC[rng.gen_range(0..ALPHANUMERIC.len())]),
+            Slot::Check => {
+                check = out.len();
+                '0'
+            }
+            Slot::Literal(c) => c,
+        };
+        out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+    // Exactly one digit in any position makes the string match.
+    for d in b'0'..=b'9' {
+        out[check] = d;
+        if pattern.matches(std::str::from_utf8(&out).unwrap()) {
+            break;
+        }
+    }
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valid;
+
+    #[test]
+    fn generated_strings_validate() {
+        let mut rng = rand::thread_rng();
+        for len in 1..=32 {
+            let s = generate(len, &mut rng);
+            assert_eq!(s.len(), len);
+            assert!(s.bytes().all(|b| b.is_ascii_digit()));
+            assert!(valid(&s), "{} doesn't validate", s);
+        }
+    }
+
+    #[test]
+    fn generated_strings_keep_their_prefix() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let s = generate_with_prefix("411111", 16, &mut rng);
+            assert_eq!(s.len(), 16);
+            assert!(s.starts_with("411111"));
+            assert!(valid(&s), "{} doesn't validate", s);
+        }
+        assert_eq!(
+            generate_with_prefix("7992739871", 11, &mut rng),
+            "79927398713"
+        );
+    }
+
+    #[test]
+    fn generate_many_writes_lines() {
+        let config = GenerateConfig {
+            prefix: "4",
+            ..GenerateConfig::default()
+        };
+        let mut out = vec![];
+        generate_many(1000, &config, &mut rand::thread_rng(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), 1000);
+        for line in out.lines() {
+            assert_eq!(line.len(), 16);
+            assert!(line.starts_with('4'));
+            assert!(valid(line), "{} doesn't validate", line);
+        }
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible() {
+        // If this changes, everyone's snapshots break.
+        assert_eq!(generate_seeded(42, 16), "2619743672613316");
+        assert_ne!(generate_seeded(42, 16), generate_seeded(43, 16));
+        assert!(valid(&generate_seeded(42, 16)));
+    }
+
+    #[test]
+    fn generators_accept_trait_objects() {
+        let rng: &mut dyn RngCore = &mut ChaCha8Rng::seed_from_u64(7);
+        assert!(valid(&generate(16, rng)));
+    }
+
+    #[test]
+    fn test_cards_belong_to_their_network() {
+        use CardNetwork::*;
+        let mut rng = rand::thread_rng();
+        for &network in &[
+            Visa,
+            Mastercard,
+            AmericanExpress,
+            Discover,
+            DinersClub,
+            Jcb,
+            UnionPay,
+        ] {
+            for _ in 0..20 {
+                let pan = generate_test_card(network, &mut rng);
+                assert_eq!(pan.network(), Some(network), "{}", pan);
+                assert!(test_bins(network).iter().any(|(bin, _)| pan.bin() == *bin));
+            }
+        }
+    }
+
+    #[test]
+    fn imeis_come_from_the_given_tacs() {
+        let tacs = ["49015420", "35209900"];
+        let imeis: Vec<Imei> = generate_imeis(&tacs, 100, &mut rand::thread_rng()).collect();
+        assert_eq!(imeis.len(), 100);
+        assert!(imeis.iter().all(|imei| tacs.contains(&imei.tac())));
+    }
+
+    #[test]
+    fn masking_keeps_the_ends_and_validates() {
+        let mut rng = rand::thread_rng();
+        for pan in &["4111111111111111", "378282246310005", "41111111113"] {
+            for _ in 0..20 {
+                let masked = mask_preserving_luhn(pan, &mut rng);
+                assert_eq!(masked.len(), pan.len());
+                assert_eq!(masked[..6], pan[..6]);
+                assert_eq!(masked[pan.len() - 4..], pan[pan.len() - 4..]);
+                assert!(valid(&masked), "{} doesn't validate", masked);
+            }
+        }
+    }
+
+    #[test]
+    fn templates_lay_out_valid_strings() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let card = generate_from_template("4111-####-####-###C", &mut rng).unwrap();
+            assert!(Pattern::new("4111-####-####-###C").unwrap().matches(&card));
+
+            let front = generate_from_template("C ##########", &mut rng).unwrap();
+            assert!(valid(&front.replace(' ', "")), "{} doesn't validate", front);
+
+            let code = generate_from_template("?????\\CX C", &mut rng).unwrap();
+            assert_eq!(&code[5..8], "CX ");
+            let body: String = code[..8].chars().filter(|&c| c != ' ').collect();
+            assert_eq!(code.as_bytes()[8], checksum(body.as_bytes()), "{}", code);
+            assert!(code[..5]
+                .bytes()
+                .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase()));
+        }
+        // Whatever's written in the template, what's generated matches it.
+        for template in ["ID-####C", "US?????????C", "?????\\CX C", "x#-C?"].iter() {
+            let pattern = Pattern::new(template).unwrap();
+            for _ in 0..20 {
+                let generated = generate_from_template(template, &mut rng).unwrap();
+                assert!(
+                    pattern.matches(&generated),
+                    "{} from {}",
+                    generated,
+                    template
+                );
+            }
+        }
+        assert_eq!(
+            generate_from_template("799-273-987-1C, ok", &mut rng),
+            Ok("799-273-987-13, ok".to_string())
+        );
+    }
+
+    #[test]
+    fn templates_need_one_check_digit() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(
+            generate_from_template("####", &mut rng),
+            Err(PatternError::NoCheckDigit)
+        );
+        assert_eq!(
+            generate_from_template("#C\\C#C", &mut rng),
+            Err(PatternError::SecondCheckDigit { position: 5 })
+        );
+        assert_eq!(
+            generate_from_template("###C\\", &mut rng),
+            Err(PatternError::TrailingBackslash)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn prefix_must_leave_room_for_check_digit() {
+        generate_with_prefix("411111", 6, &mut rand::thread_rng());
+    }
+}
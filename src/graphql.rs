@@ -0,0 +1,127 @@
+//! async-graphql scalars for the newtypes.
+//!
+//! Card numbers are masked in responses (see [`Pan::masked`]), so a
+//! `Pan` field never leaks a full card number through a GraphQL API.
+//! If a client really needs the full number, choose that per field by
+//! wrapping it in an [`UnmaskedPan`], a separate `UnmaskedPan` scalar in
+//! the schema:
+//!
+//! ```
+//! use luhn::graphql::UnmaskedPan;
+//! use luhn::Pan;
+//!
+//! #[derive(async_graphql::SimpleObject)]
+//! struct Card {
+//!     /// For display: `"************1111"`.
+//!     number: Pan,
+//!     /// For the payment processor: `"4111111111111111"`.
+//!     full_number: UnmaskedPan,
+//! }
+//!
+//! let pan = Pan::new("4111111111111111").unwrap();
+//! let card = Card {
+//!     number: pan.clone(),
+//!     full_number: UnmaskedPan(pan),
+//! };
+//! ```
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+use crate::{Isin, Pan};
+
+/// A payment card number which, unlike [`Pan`], is written out in full.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnmaskedPan(pub Pan);
+
+impl From<Pan> for UnmaskedPan {
+    fn from(pan: Pan) -> Self {
+        UnmaskedPan(pan)
+    }
+}
+
+/// A payment card number.  Validated on input, masked on output.
+#[Scalar(name = "Pan")]
+impl ScalarType for Pan {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Pan::new(s)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.masked())
+    }
+}
+
+/// A payment card number.  Validated on input, written in full on output.
+#[Scalar(name = "UnmaskedPan")]
+impl ScalarType for UnmaskedPan {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        Ok(UnmaskedPan(
+            Pan::parse(value).map_err(InputValueError::propagate)?,
+        ))
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        Pan::is_valid(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.as_str().to_string())
+    }
+}
+
+/// An International Securities Identification Number.
+#[Scalar(name = "Isin")]
+impl ScalarType for Isin {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Isin::new(s)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.as_str().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pans_are_masked_on_output() {
+        let pan = Pan::parse(Value::String("4111111111111111".into())).unwrap();
+        assert_eq!(
+            pan.to_value(),
+            Value::String("************1111".to_string())
+        );
+        assert!(Pan::parse(Value::String("4111111111111112".into())).is_err());
+        assert!(Pan::parse(Value::Number(4.into())).is_err());
+    }
+
+    #[test]
+    fn unmasked_pans_round_trip() {
+        let value = Value::String("4111111111111111".into());
+        let pan = UnmaskedPan::parse(value.clone()).unwrap();
+        assert_eq!(pan.to_value(), value);
+        assert!(UnmaskedPan::parse(Value::String("4111111111111112".into())).is_err());
+    }
+
+    #[test]
+    fn isins_round_trip() {
+        let value = Value::String("US0378331005".into());
+        let isin = Isin::parse(value.clone()).unwrap();
+        assert_eq!(isin.to_value(), value);
+    }
+}
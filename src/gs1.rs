@@ -0,0 +1,219 @@
+//! GS1 identification keys, such as the GTINs in retail barcodes.
+//!
+//! GS1 check digits weight the digits alternately by 3 and 1, counting
+//! from the right, and pick the digit which rounds the sum up to a
+//! multiple of ten.  Like Luhn, leading zeros don't change the result.
+//!
+//! ```
+//! use luhn::gs1::{self, Gtin, GtinKind};
+//!
+//! assert_eq!(gs1::checksum(b"400638133393"), b'1');
+//! let gtin: Gtin = "4006381333931".parse().unwrap();
+//! assert_eq!(gtin.kind(), GtinKind::Gtin13);
+//! assert_eq!(gtin.to_kind(GtinKind::Gtin14).unwrap().as_str(), "04006381333931");
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{check_digits_with, LuhnError};
+
+/// Computes the GS1 check digit for `body`.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    let sum: u32 = body
+        .iter()
+        .rev()
+        .zip([3, 1].iter().cycle())
+        .map(|(&b, &weight)| {
+            assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+            u32::from(b - b'0') * weight
+        })
+        .sum();
+    (10 - sum % 10) as u8 % 10 + b'0'
+}
+
+/// Whether `s` is ASCII digits ending in a good GS1 check digit.  Any
+/// length is accepted.
+pub fn valid(s: &str) -> bool {
+    check(s).is_ok()
+}
+
+/// Like [`valid`], but says what's wrong.
+pub(crate) fn check(s: &str) -> Result<(), LuhnError> {
+    check_digits_with(s, checksum)
+}
+
+/// The width of a [`Gtin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GtinKind {
+    /// EAN-8, for small packages.
+    Gtin8,
+    /// UPC-A, used in North America.
+    Gtin12,
+    /// EAN-13, used everywhere else.
+    Gtin13,
+    /// Trade items such as cases, often printed on cartons.
+    Gtin14,
+}
+
+impl GtinKind {
+    /// The number of digits in this kind of GTIN.
+    pub fn width(self) -> usize {
+        match self {
+            GtinKind::Gtin8 => 8,
+            GtinKind::Gtin12 => 12,
+            GtinKind::Gtin13 => 13,
+            GtinKind::Gtin14 => 14,
+        }
+    }
+
+    /// The kind of GTIN which has `width` digits, if any.
+    pub fn from_width(width: usize) -> Option<GtinKind> {
+        match width {
+            8 => Some(GtinKind::Gtin8),
+            12 => Some(GtinKind::Gtin12),
+            13 => Some(GtinKind::Gtin13),
+            14 => Some(GtinKind::Gtin14),
+            _ => None,
+        }
+    }
+}
+
+/// A validated Global Trade Item Number of any width.
+///
+/// The kind is detected from the length.  Widths convert into each other
+/// by adding or removing leading zeros, which keeps the check digit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Gtin(String);
+
+impl Gtin {
+    /// Validates `gtin`, which must be 8, 12, 13 or 14 digits.
+    ///
+    /// A length between the widths is reported as too short for the next
+    /// width up.
+    pub fn new(gtin: &str) -> Result<Gtin, LuhnError> {
+        Gtin::validate(gtin)?;
+        Ok(Gtin(gtin.to_string()))
+    }
+
+    /// Checks `gtin` as [`new`](Gtin::new) does, without keeping it.
+    pub(crate) fn validate(gtin: &str) -> Result<(), LuhnError> {
+        let len = gtin.len();
+        if GtinKind::from_width(len).is_none() {
+            return Err(match [8, 12, 13, 14].iter().find(|&&min| len < min) {
+                Some(&min) => LuhnError::TooShort { len, min },
+                None => LuhnError::TooLong { len, max: 14 },
+            });
+        }
+        check(gtin)?;
+        Ok(())
+    }
+
+    /// The GTIN as a string of digits.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Which width this GTIN is.
+    pub fn kind(&self) -> GtinKind {
+        GtinKind::from_width(self.0.len()).unwrap()
+    }
+
+    /// The same GTIN written with `kind`'s width.
+    ///
+    /// Widening pads with leading zeros.  Narrowing removes them, and
+    /// returns `None` if the digits to be removed aren't all zeros.
+    pub fn to_kind(&self, kind: GtinKind) -> Option<Gtin> {
+        let width = kind.width();
+        let digits = self.0.trim_start_matches('0');
+        if digits.len() > width {
+            return None;
+        }
+        Some(Gtin(format!("{:0>width$}", digits, width = width)))
+    }
+}
+
+impl FromStr for Gtin {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Gtin, LuhnError> {
+        Gtin::new(s)
+    }
+}
+
+impl fmt::Display for Gtin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Gtin {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Gtin {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_check_digits() {
+        assert_eq!(checksum(b"9638507"), b'4');
+        assert_eq!(checksum(b"03600029145"), b'2');
+        assert_eq!(checksum(b"400638133393"), b'1');
+        assert_eq!(checksum(b"1001234500001"), b'7');
+        assert_eq!(checksum(b""), b'0');
+        assert!(valid("036000291452"));
+        assert!(!valid("036000291453"));
+        assert!(!valid("03600029145x"));
+    }
+
+    #[test]
+    fn detects_kinds() {
+        let kind = |s: &str| Gtin::new(s).map(|g| g.kind());
+        assert_eq!(kind("96385074"), Ok(GtinKind::Gtin8));
+        assert_eq!(kind("036000291452"), Ok(GtinKind::Gtin12));
+        assert_eq!(kind("4006381333931"), Ok(GtinKind::Gtin13));
+        assert_eq!(kind("10012345000017"), Ok(GtinKind::Gtin14));
+        assert_eq!(
+            kind("963850742"),
+            Err(LuhnError::TooShort { len: 9, min: 12 })
+        );
+        assert_eq!(
+            kind("100123450000170"),
+            Err(LuhnError::TooLong { len: 15, max: 14 })
+        );
+        assert_eq!(
+            kind("4006381333932"),
+            Err(LuhnError::BadCheckDigit {
+                position: 12,
+                expected: '1',
+                found: '2'
+            })
+        );
+    }
+
+    #[test]
+    fn converts_between_widths() {
+        let upc = Gtin::new("036000291452").unwrap();
+        let gtin14 = upc.to_kind(GtinKind::Gtin14).unwrap();
+        assert_eq!(gtin14.as_str(), "00036000291452");
+        assert_eq!(Gtin::new(gtin14.as_str()), Ok(gtin14.clone()));
+        assert_eq!(gtin14.to_kind(GtinKind::Gtin12), Some(upc.clone()));
+        assert_eq!(
+            gtin14.to_kind(GtinKind::Gtin13).unwrap().as_str(),
+            "0036000291452"
+        );
+        assert_eq!(upc.to_kind(GtinKind::Gtin8), None);
+    }
+}
@@ -0,0 +1,58 @@
+//! Airline document numbers, such as tickets and air waybills.
+//!
+//! The check digit is the serial number, read as an integer, mod 7.  It
+//! covers the serial number only, so leave out the three-digit airline
+//! prefix: for the air waybill `020-12345675`, validate `"12345675"`.
+//!
+//! ```
+//! use luhn::iata;
+//!
+//! assert!(iata::valid("12345675"));
+//! assert_eq!(iata::checksum(b"1234567"), b'5');
+//! ```
+
+use crate::check_digits_with;
+
+/// Computes the check digit for a serial number.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(serial: &[u8]) -> u8 {
+    let rem = serial.iter().fold(0, |rem, &b| {
+        assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+        (rem * 10 + (b - b'0')) % 7
+    });
+    rem + b'0'
+}
+
+/// Whether `s` is ASCII digits ending in a good mod-7 check digit.
+pub fn valid(s: &str) -> bool {
+    s.len() >= 2 && check_digits_with(s, checksum).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_serial_numbers() {
+        assert!(valid("12345675"));
+        assert!(!valid("12345670"));
+        assert!(valid("00"));
+        assert!(valid("70"));
+        assert!(!valid("0"));
+        assert!(!valid("1234567-5"));
+    }
+
+    #[test]
+    fn agrees_with_integer_arithmetic() {
+        for serial in [0u64, 6, 7, 999_999_999, 1_234_567_890, u64::MAX / 10] {
+            let digits = serial.to_string();
+            assert_eq!(
+                checksum(digits.as_bytes()),
+                (serial % 7) as u8 + b'0',
+                "{}",
+                serial
+            );
+        }
+    }
+}
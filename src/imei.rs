@@ -0,0 +1,111 @@
+//! International Mobile Equipment Identities.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{check_digits, LuhnError};
+
+pub(crate) const LEN: usize = 15;
+
+/// A validated 15-digit IMEI.
+///
+/// An IMEI is an 8-digit type allocation code (TAC), a 6-digit serial
+/// number, and a Luhn check digit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
+pub struct Imei(pub(crate) String);
+
+impl Imei {
+    /// Validates `imei`.
+    pub fn new(imei: &str) -> Result<Imei, LuhnError> {
+        Imei::validate(imei)?;
+        Ok(Imei(imei.to_string()))
+    }
+
+    /// Checks `imei` as [`new`](Imei::new) does, without keeping it.
+    pub(crate) fn validate(imei: &str) -> Result<(), LuhnError> {
+        let len = imei.len();
+        if len < LEN {
+            return Err(LuhnError::TooShort { len, min: LEN });
+        }
+        if len > LEN {
+            return Err(LuhnError::TooLong { len, max: LEN });
+        }
+        check_digits(imei)?;
+        Ok(())
+    }
+
+    /// The IMEI as a string of digits.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The type allocation code, which identifies the device model.
+    pub fn tac(&self) -> &str {
+        &self.0[..8]
+    }
+
+    /// The serial number assigned by the manufacturer.
+    pub fn serial(&self) -> &str {
+        &self.0[8..14]
+    }
+}
+
+impl FromStr for Imei {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Imei, LuhnError> {
+        Imei::new(s)
+    }
+}
+
+impl fmt::Display for Imei {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Imei {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Imei {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_good_imeis() {
+        let imei: Imei = "490154203237518".parse().unwrap();
+        assert_eq!(imei.tac(), "49015420");
+        assert_eq!(imei.serial(), "323751");
+    }
+
+    #[test]
+    fn rejects_bad_imeis() {
+        assert_eq!(
+            Imei::new("4901542032375"),
+            Err(LuhnError::TooShort { len: 13, min: 15 })
+        );
+        assert_eq!(
+            Imei::new("490154203237519"),
+            Err(LuhnError::BadCheckDigit {
+                position: 14,
+                expected: '8',
+                found: '9'
+            })
+        );
+    }
+}
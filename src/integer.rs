@@ -0,0 +1,68 @@
+//! Validating numbers stored as integers.
+
+use crate::sequence::check_digit;
+
+/// Validates the decimal digits of `n`, the last of which is the check
+/// digit.
+///
+/// ```
+/// assert!(luhn::valid_u64(79927398713));
+/// assert!(!luhn::valid_u64(79927398710));
+/// ```
+pub fn valid_u64(n: u64) -> bool {
+    check_digit(n / 10) == n % 10
+}
+
+/// Validates `n` written as exactly `width` digits, padded with leading
+/// zeros.
+///
+/// Identifiers such as ICCIDs and account numbers often start with zeros,
+/// which an integer can't hold.  Leading zeros don't change the Luhn sum,
+/// so this agrees with [`valid_u64`] whenever `n` fits; the difference is
+/// that a number needing more than `width` digits is rejected, as is a
+/// `width` of zero.
+///
+/// ```
+/// // "0079927398713"
+/// assert!(luhn::valid_u64_width(79927398713, 13));
+/// assert!(!luhn::valid_u64_width(79927398713, 10));
+/// ```
+pub fn valid_u64_width(n: u64, width: usize) -> bool {
+    width > 0 && decimal_len(n) <= width && valid_u64(n)
+}
+
+/// The number of decimal digits needed to write `n`.
+pub(crate) fn decimal_len(n: u64) -> usize {
+    n.checked_ilog10().map_or(1, |log| log as usize + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valid;
+
+    #[test]
+    fn agrees_with_padded_strings() {
+        for n in (0..2000).chain(u64::MAX - 100..=u64::MAX) {
+            assert_eq!(valid_u64(n), valid(&n.to_string()), "{}", n);
+            for width in 0..=21 {
+                let padded = format!("{:0width$}", n, width = width);
+                assert_eq!(
+                    valid_u64_width(n, width),
+                    width > 0 && padded.len() == width && valid(&padded),
+                    "{} in {} digits",
+                    n,
+                    width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn decimal_lengths() {
+        assert_eq!(decimal_len(0), 1);
+        assert_eq!(decimal_len(9), 1);
+        assert_eq!(decimal_len(10), 2);
+        assert_eq!(decimal_len(u64::MAX), 20);
+    }
+}
@@ -0,0 +1,184 @@
+//! International Securities Identification Numbers.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{checksum, strip_grouping, LuhnError};
+
+const LEN: usize = 12;
+
+/// A validated ISIN, eg. `US0378331005`.
+///
+/// An ISIN is a two-letter country code, a nine-character alphanumeric
+/// national security identifier (NSIN), and a check digit computed with
+/// [`checksum`](crate::checksum).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
+pub struct Isin(pub(crate) String);
+
+impl Isin {
+    /// Validates `isin`.  Letters must be uppercase.
+    pub fn new(isin: &str) -> Result<Isin, LuhnError> {
+        Isin::validate(isin)?;
+        Ok(Isin(isin.to_string()))
+    }
+
+    /// Checks `isin` as [`new`](Isin::new) does, without keeping it.
+    pub(crate) fn validate(isin: &str) -> Result<(), LuhnError> {
+        let len = isin.len();
+        if len < LEN {
+            return Err(LuhnError::TooShort { len, min: LEN });
+        }
+        if len > LEN {
+            return Err(LuhnError::TooLong { len, max: LEN });
+        }
+        for (position, character) in isin.char_indices() {
+            let ok = match position {
+                0..=1 => character.is_ascii_uppercase(),
+                2..=10 => character.is_ascii_uppercase() || character.is_ascii_digit(),
+                _ => character.is_ascii_digit(),
+            };
+            if !ok {
+                return Err(LuhnError::InvalidCharacter {
+                    position,
+                    character,
+                });
+            }
+        }
+        let bytes = isin.as_bytes();
+        let expected = checksum(&bytes[..LEN - 1]);
+        if bytes[LEN - 1] != expected {
+            return Err(LuhnError::BadCheckDigit {
+                position: LEN - 1,
+                expected: expected as char,
+                found: bytes[LEN - 1] as char,
+            });
+        }
+        Ok(())
+    }
+
+    /// Generates a random ISIN with the given country code.
+    ///
+    /// The NSIN is nine random uppercase alphanumeric characters, so the
+    /// result is structurally valid but almost certainly not a real
+    /// security.  Panics if `country` isn't two uppercase ASCII letters.
+    #[cfg(feature = "rand")]
+    pub fn generate<R: rand::RngCore + ?Sized>(country: &str, rng: &mut R) -> Isin {
+        use rand::Rng;
+
+        const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        assert!(
+            country.len() == 2 && country.bytes().all(|b| b.is_ascii_uppercase()),
+            "Not a country code: {:?}",
+            country
+        );
+        let mut isin = country.as_bytes().to_vec();
+        isin.extend((0..9).map(|_| ALPHANUMERIC[rng.gen_range(0..ALPHANUMERIC.len())]));
+        isin.push(checksum(&isin));
+        Isin(String::from_utf8(isin).unwrap())
+    }
+
+    /// The ISIN as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The ISO 3166-1 alpha-2 code of the issuing country, eg. `"US"`.
+    pub fn country_code(&self) -> &str {
+        &self.0[..2]
+    }
+
+    /// The national security identifier, eg. a CUSIP in the US.
+    pub fn nsin(&self) -> &str {
+        &self.0[2..11]
+    }
+}
+
+/// Parses like [`Isin::new`], but allows the country code, NSIN and check
+/// digit to be split up with spaces or hyphens, as in `"US-037833100-5"`.
+/// Error positions refer to the ISIN without them.
+impl FromStr for Isin {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Isin, LuhnError> {
+        Isin::new(&strip_grouping(s))
+    }
+}
+
+impl fmt::Display for Isin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Isin {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Isin {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_good_isins() {
+        let isin: Isin = "US0378331005".parse().unwrap();
+        assert_eq!(isin.country_code(), "US");
+        assert_eq!(isin.nsin(), "037833100");
+        assert!(Isin::new("BMG491BT1088").is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_isins() {
+        assert_eq!(
+            Isin::new("US037833100"),
+            Err(LuhnError::TooShort { len: 11, min: 12 })
+        );
+        assert_eq!(
+            Isin::new("us0378331005"),
+            Err(LuhnError::InvalidCharacter {
+                position: 0,
+                character: 'u'
+            })
+        );
+        assert_eq!(
+            Isin::new("US0378331000"),
+            Err(LuhnError::BadCheckDigit {
+                position: 11,
+                expected: '5',
+                found: '0'
+            })
+        );
+    }
+
+    #[test]
+    fn parses_grouped_isins() {
+        let isin = Isin::new("US0378331005").unwrap();
+        assert_eq!("US-037833100-5".parse(), Ok(isin.clone()));
+        assert_eq!(" US 0378 3310 05 ".parse(), Ok(isin));
+        assert!(Isin::new("US-037833100-5").is_err());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_isins_validate() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let isin = Isin::generate("GB", &mut rng);
+            assert_eq!(isin.country_code(), "GB");
+            assert_eq!(Isin::new(isin.as_str()), Ok(isin));
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Check digit schemes from ISO/IEC 7064.
+//!
+//! The standard defines a family of schemes, each named after its
+//! moduli; there's a module for each one supported.
+
+pub mod mod_11_10;
+pub mod mod_97_10;
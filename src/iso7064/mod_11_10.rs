@@ -0,0 +1,60 @@
+//! ISO/IEC 7064 MOD 11,10, a hybrid scheme with a single decimal check
+//! digit, used by Croatian OIBs and German tax numbers among others.
+//!
+//! ```
+//! use luhn::iso7064::mod_11_10;
+//!
+//! assert!(mod_11_10::valid("69435151530"));
+//! assert_eq!(mod_11_10::checksum(b"6943515153"), b'0');
+//! ```
+
+use crate::check_digits_with;
+
+/// Computes the check digit for `body`.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    let product = body.iter().fold(10, |product, &b| {
+        assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+        let sum = match (product + (b - b'0')) % 10 {
+            0 => 10,
+            sum => sum,
+        };
+        sum * 2 % 11
+    });
+    (11 - product) % 10 + b'0'
+}
+
+/// Whether `s` is ASCII digits ending in a good check digit.
+pub fn valid(s: &str) -> bool {
+    check_digits_with(s, checksum).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_check_digits() {
+        assert_eq!(checksum(b"6943515153"), b'0');
+        assert_eq!(checksum(b"9457740319"), b'4');
+        assert_eq!(checksum(b"0000000000"), b'1');
+        assert!(valid("94577403194"));
+        assert!(!valid("94577403195"));
+        assert!(!valid(""));
+    }
+
+    #[test]
+    fn catches_single_digit_errors() {
+        let good = b"94577403194";
+        for i in 0..good.len() {
+            for d in b'0'..=b'9' {
+                if d != good[i] {
+                    let mut bad = *good;
+                    bad[i] = d;
+                    assert!(!valid(std::str::from_utf8(&bad).unwrap()));
+                }
+            }
+        }
+    }
+}
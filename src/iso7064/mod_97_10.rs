@@ -0,0 +1,65 @@
+//! ISO/IEC 7064 MOD 97-10, which appends two check digits, used by IBANs
+//! and ISO 11649 creditor references among others.
+//!
+//! Letters count as two digits, `A` being 10 and `Z` 35, so the scheme
+//! works on alphanumeric input.
+//!
+//! ```
+//! use luhn::iso7064::mod_97_10;
+//!
+//! assert_eq!(&mod_97_10::checksum(b"123456"), b"76");
+//! assert!(mod_97_10::valid("12345676"));
+//! ```
+
+/// Computes the two check digits for `body`.
+///
+/// Input must be uppercase alphanumeric ASCII; panics otherwise.  Returns
+/// ASCII digits.
+pub fn checksum(body: &[u8]) -> [u8; 2] {
+    let check = 98 - remainder(body) * 100 % 97;
+    [(check / 10) as u8 + b'0', (check % 10) as u8 + b'0']
+}
+
+/// Whether `s` is uppercase alphanumeric ASCII ending in two good check
+/// digits.
+pub fn valid(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() > 2
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        && bytes[bytes.len() - 2..].iter().all(u8::is_ascii_digit)
+        && remainder(bytes) == 1
+}
+
+/// `body` read as an integer, mod 97.
+fn remainder(body: &[u8]) -> u32 {
+    body.iter().fold(0, |rem, &c| match c {
+        b'0'..=b'9' => (rem * 10 + u32::from(c - b'0')) % 97,
+        b'A'..=b'Z' => (rem * 100 + u32::from(c - b'A' + 10)) % 97,
+        _ => panic!("Not alphanumeric: {}", c),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_check_digits() {
+        assert_eq!(&checksum(b"123456"), b"76");
+        assert_eq!(&checksum(b"539007547034RF"), b"18");
+        assert!(valid("12345676"));
+        assert!(!valid("12345677"));
+        assert!(!valid("76"));
+        assert!(!valid("123456a6"));
+    }
+
+    #[test]
+    fn validates_ibans() {
+        // IBANs move the country code and check digits to the end.
+        let iban = "GB82WEST12345698765432";
+        let rearranged = format!("{}{}", &iban[4..], &iban[..4]);
+        assert!(valid(&rearranged));
+    }
+}
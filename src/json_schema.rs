@@ -0,0 +1,77 @@
+//! `JsonSchema` implementations for the newtypes.
+
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{Imei, Isin, Pan};
+
+impl JsonSchema for Pan {
+    fn schema_name() -> Cow<'static, str> {
+        "Pan".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": "^[0-9]{12,19}$",
+            "minLength": 12,
+            "maxLength": 19,
+            "description": "A payment card number.  The last digit is a Luhn check digit.",
+        })
+    }
+}
+
+impl JsonSchema for Isin {
+    fn schema_name() -> Cow<'static, str> {
+        "Isin".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": "^[A-Z]{2}[0-9A-Z]{9}[0-9]$",
+            "minLength": 12,
+            "maxLength": 12,
+            "description": "An International Securities Identification Number.  The last \
+                digit is a Luhn check digit, computed after converting letters to numbers.",
+        })
+    }
+}
+
+impl JsonSchema for Imei {
+    fn schema_name() -> Cow<'static, str> {
+        "Imei".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": "^[0-9]{15}$",
+            "minLength": 15,
+            "maxLength": 15,
+            "description": "An International Mobile Equipment Identity.  The last digit is a \
+                Luhn check digit.",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_have_patterns() {
+        let schema = schemars::schema_for!(Pan);
+        assert_eq!(schema.get("pattern").unwrap(), "^[0-9]{12,19}$");
+        assert_eq!(schema.get("type").unwrap(), "string");
+        let schema = schemars::schema_for!(Isin);
+        assert_eq!(schema.get("maxLength").unwrap(), 12);
+        assert!(schema
+            .get("description")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .contains("The last digit is a Luhn check digit, computed"));
+    }
+}
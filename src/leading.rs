@@ -0,0 +1,112 @@
+//! Identifiers with the Luhn check digit at the front, as some loyalty
+//! and membership schemes use.
+//!
+//! The digits are weighted outwards from the check digit, so a number
+//! with a leading check digit is valid exactly when its mirror image
+//! passes the usual Luhn check.  This is the layout
+//! [`CheckPosition::First`](crate::spec::CheckPosition::First) describes.
+
+use crate::LuhnError;
+
+/// Whether `s` is at least one ASCII digit, starting with a good Luhn
+/// check digit.
+///
+/// ```
+/// assert!(luhn::valid_leading_check("47992739871"));
+/// assert!(!luhn::valid_leading_check("4111111111111111"));
+/// ```
+pub fn valid_leading_check(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes().all(|b| b.is_ascii_digit())
+        && check_for(&s.as_bytes()[1..]) == s.as_bytes()[0]
+}
+
+/// Puts the Luhn check digit for `body` in front of it, so that the
+/// result passes [`valid_leading_check`].
+///
+/// Fails if `body` has anything other than ASCII digits.
+///
+/// ```
+/// assert_eq!(luhn::prepend_checksum("7992739871").unwrap(), "47992739871");
+/// ```
+pub fn prepend_checksum(body: &str) -> Result<String, LuhnError> {
+    if let Some((position, character)) = body.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(LuhnError::InvalidCharacter {
+            position,
+            character,
+        });
+    }
+    let mut number = String::with_capacity(body.len() + 1);
+    number.push(check_for(body.as_bytes()) as char);
+    number.push_str(body);
+    Ok(number)
+}
+
+/// The leading check digit for the ASCII digits `body`, as an ASCII
+/// digit: [`checksum`](crate::checksum) of its mirror image, worked out
+/// from the front.
+fn check_for(body: &[u8]) -> u8 {
+    let sum: u32 = body
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            let d = u32::from(b - b'0');
+            if i % 2 == 0 {
+                d / 5 + (2 * d) % 10
+            } else {
+                d
+            }
+        })
+        .sum();
+    ((10 - sum % 10) % 10) as u8 + b'0'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{CheckPosition, SchemeSpec};
+    use crate::Validator;
+
+    #[test]
+    fn agrees_with_the_leading_check_spec() {
+        let scheme = SchemeSpec {
+            name: "luhn-leading".to_string(),
+            alphabet: "0123456789".to_string(),
+            check_alphabet: None,
+            weights: vec![2, 1],
+            modulus: 10,
+            sum_product_digits: true,
+            check_position: CheckPosition::First,
+            min_len: 1,
+            max_len: None,
+        }
+        .build()
+        .unwrap();
+        for n in (0..100_000u64).step_by(13) {
+            let body = n.to_string();
+            let number = prepend_checksum(&body).unwrap();
+            assert_eq!(Ok(number.chars().next().unwrap()), scheme.checksum(&body));
+            assert!(valid_leading_check(&number), "{}", number);
+            assert_eq!(
+                valid_leading_check(&body),
+                scheme.validate(&body),
+                "{}",
+                body
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_digits() {
+        assert!(!valid_leading_check(""));
+        assert!(!valid_leading_check("4 7992739871"));
+        assert_eq!(prepend_checksum(""), Ok("0".to_string()));
+        assert_eq!(
+            prepend_checksum("7992-739871"),
+            Err(LuhnError::InvalidCharacter {
+                position: 4,
+                character: '-'
+            })
+        );
+    }
+}
@@ -3,17 +3,145 @@
 It's not a great checksum, but it's used in a bunch of places (credit
 card numbers, ISIN codes, etc.).  More information is available on
 [wikipedia](https://en.wikipedia.org/wiki/Luhn_algorithm).
+
+Checking an identifier or computing its check digit doesn't allocate for
+input of up to 32 bytes, which covers card numbers, ISINs, IMEIs and the
+other identifiers here, and functions which return a `String` allocate
+only that.  Longer input works the same, but may allocate.
 */
 
-use digits_iterator::DigitsExtension;
+use small::SmallString;
+
+mod algorithm;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod br;
+mod bulk;
+pub mod ch_esr;
+mod confusables;
+#[cfg(feature = "csv")]
+mod csv_column;
+pub mod damm;
+#[cfg(feature = "diesel")]
+mod diesel_types;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fi_reference;
+#[cfg(feature = "mmap")]
+mod file;
+#[cfg(feature = "garde")]
+pub mod garde;
+#[cfg(feature = "rand")]
+mod generate;
+#[cfg(feature = "async-graphql")]
+pub mod graphql;
+pub mod gs1;
+pub mod iata;
+mod imei;
+mod integer;
+mod isin;
+pub mod iso7064;
+#[cfg(feature = "schemars")]
+mod json_schema;
+mod leading;
+mod lines;
+pub mod medicare_au;
+#[cfg(feature = "uniffi")]
+mod mobile;
+pub mod mod_n;
+mod monitor;
+pub mod mpan;
+pub mod nif;
+pub mod no_id;
+pub mod npi;
+pub mod oib;
+#[cfg(feature = "utoipa")]
+mod openapi;
+mod pan;
+pub mod pattern;
+pub mod pesel;
+#[cfg(feature = "python")]
+mod python;
+pub mod rules;
+pub mod scan;
+mod scheme;
+mod sequence;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "serde_with")]
+pub mod serde_with;
+mod small;
+pub mod spec;
+#[cfg(feature = "sqlx")]
+mod sqlx_types;
+pub mod sscc;
+mod stats;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+pub mod tckn;
+#[cfg(any(feature = "quickcheck", feature = "arbitrary"))]
+mod testing;
+#[cfg(feature = "tokenize")]
+pub mod tokenize;
+pub mod tracking;
+pub mod uic;
+#[cfg(feature = "validator")]
+pub mod validator;
+mod validity;
+pub mod verhoeff;
+pub mod vin;
+pub mod voucher;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use algorithm::Algorithm;
+pub use bulk::{valid_fixed_records, valid_many};
+pub use confusables::Confusables;
+#[cfg(feature = "csv")]
+pub use csv_column::{filter_csv_column, validate_csv_column, CsvReport};
+pub use error::{ErrorKind, LuhnError};
+#[cfg(feature = "mmap")]
+pub use file::{validate_file, validate_file_monitored, FileConfig, FileReport};
+#[cfg(all(feature = "mmap", feature = "parallel"))]
+pub use file::{validate_file_parallel, validate_file_parallel_monitored};
+#[cfg(feature = "rand")]
+pub use generate::{
+    generate, generate_from_template, generate_imeis, generate_many, generate_seeded,
+    generate_test_card, generate_with_prefix, mask_preserving_luhn, GenerateConfig,
+};
+pub use imei::Imei;
+pub use integer::{valid_u64, valid_u64_width};
+pub use isin::Isin;
+pub use leading::{prepend_checksum, valid_leading_check};
+pub use lines::{validate_lines, validate_lines_monitored, LineReport};
+#[cfg(feature = "parallel")]
+pub use lines::{validate_lines_parallel, validate_lines_parallel_monitored};
+pub use mod_n::{
+    checksum_base62, checksum_crockford, checksum_hex, valid_base62, valid_crockford, valid_hex,
+};
+pub use monitor::{CancelToken, Monitor, Progress};
+pub use pan::{CardNetwork, Pan};
+pub use scheme::{scheme, schemes, Validator};
+pub use sequence::{
+    count_valid_in_range, next_valid, nth_valid, prev_valid, sequence, valid_range,
+};
+pub use stats::{Stats, StatsReport};
+#[cfg(any(feature = "quickcheck", feature = "arbitrary"))]
+pub use testing::{InvalidLuhn, ValidLuhn};
+pub use validity::{validate_with, validity, ValidateConfig, Validity};
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 /// Validates the given string using the Luhn algorithm.
 ///
 /// Typically such strings end in a check digit which is chosen in order
-/// to make the whole string validate.
+/// to make the whole string validate.  The empty string is valid, as is
+/// `"0"`; see [`validity`] for a stricter alternative.  Characters other
+/// than ASCII digits are skipped.
 pub fn valid(pan: &str) -> bool {
-    let mut numbers = string_to_ints(pan);
-    numbers.reverse();
+    let numbers = pan.chars().rev().filter_map(|c| c.to_digit(10));
     let mut is_odd: bool = true;
     let mut odd_sum: u32 = 0;
     let mut even_sum: u32 = 0;
@@ -29,16 +157,64 @@ pub fn valid(pan: &str) -> bool {
     (odd_sum + even_sum) % 10 == 0
 }
 
-fn string_to_ints(string: &str) -> Vec<u32> {
-    let mut numbers = vec![];
-    for c in string.chars() {
-        let value = c.to_string().parse::<u32>();
-        match value {
-            Ok(v) => numbers.push(v),
-            Err(e) => println!("error parsing number: {:?}", e),
-        }
+/// Shows the first six characters of `number`, masking the rest, for
+/// `tracing` events, which never include whole numbers.
+#[cfg(feature = "tracing")]
+pub(crate) fn masked_prefix(number: &str) -> String {
+    number
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i < 6 { c } else { '*' })
+        .collect()
+}
+
+/// Removes the spaces and hyphens people group identifiers with, eg. in
+/// `"4111 1111 1111 1111"` or `"US-037833100-5"`, and any surrounding
+/// whitespace.
+pub(crate) fn strip_grouping(s: &str) -> SmallString {
+    s.trim().chars().filter(|&c| c != ' ' && c != '-').collect()
+}
+
+/// Checks that `digits` is all ASCII digits and ends in a good check digit.
+pub(crate) fn check_digits(digits: &str) -> Result<(), LuhnError> {
+    check_digits_with(digits, checksum)
+}
+
+/// Like [`check_digits`], but with the check digit computed by another
+/// scheme's `checksum`, which returns an ASCII character.
+pub(crate) fn check_digits_with(
+    digits: &str,
+    checksum: impl Fn(&[u8]) -> u8,
+) -> Result<(), LuhnError> {
+    if let Some((position, character)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(LuhnError::InvalidCharacter {
+            position,
+            character,
+        });
+    }
+    let bytes = digits.as_bytes();
+    let position = match bytes.len().checked_sub(1) {
+        Some(position) => position,
+        None => return Err(LuhnError::TooShort { len: 0, min: 1 }),
+    };
+    let expected = checksum(&bytes[..position]);
+    if bytes[position] != expected {
+        return Err(LuhnError::BadCheckDigit {
+            position,
+            expected: expected as char,
+            found: bytes[position] as char,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that `s` is exactly `len` characters long.
+pub(crate) fn check_len(s: &str, len: usize) -> Result<(), LuhnError> {
+    match s.len() {
+        n if n < len => Err(LuhnError::TooShort { len: n, min: len }),
+        n if n > len => Err(LuhnError::TooLong { len: n, max: len }),
+        _ => Ok(()),
     }
-    numbers
 }
 
 /// Computes the Luhn check digit for the given string.
@@ -59,51 +235,218 @@ pub fn checksum(input: &[u8]) -> u8 {
         }
     }
 
-    // Encode the chars in the input and concatenate them digit-wise.
-    // Eg. "3C" => [3, 1, 2]
-    // FIXME: This allocates.  Is it necessary?
-    // One char may become two digits => max length is input.len() * 2.
-    let mut ds = Vec::<u8>::with_capacity(input.len() * 2);
-    ds.extend(
-        input
-            .iter()
-            .copied()
-            .map(encode_char)
-            .flat_map(DigitsExtension::digits),
-    );
-
-    // The even-indexed digits, as numbered from the back, are added digit-wise.
-    let checksum_even = ds
-        .iter()
-        .rev()
-        .skip(1)
-        .step_by(2)
-        .copied()
-        .flat_map(DigitsExtension::digits)
-        .sum::<u8>();
-
-    // The odd-indexed digits, as numbered from the back, are doubled first.
-    let checksum_odd = ds
-        .iter()
-        .rev()
-        .step_by(2)
-        .map(|&x| x * 2)
-        .flat_map(DigitsExtension::digits)
-        .sum::<u8>();
-
-    let checksum = checksum_even + checksum_odd;
+    // Each char stands for the digits of its index, concatenated, eg.
+    // "3C" => [3, 1, 2].  Working from the back, the digits are doubled
+    // and added digit-wise in turn, starting with the last, since the
+    // check digit goes after it.
+    let mut checksum = 0;
+    let mut double = true;
+    for &c in input.iter().rev() {
+        let value = u32::from(encode_char(c));
+        let digits = [value % 10, value / 10];
+        for &d in &digits[..if value < 10 { 1 } else { 2 }] {
+            checksum += if double { d / 5 + (2 * d) % 10 } else { d };
+            double = !double;
+        }
+    }
 
     // (checksum + luhn digit) % 10 must be zero.  Working backwards:
-    let digit = (10 - (checksum % 10)) % 10;
+    let digit = ((10 - (checksum % 10)) % 10) as u8;
 
     // convert to ASCII
     digit + 48
 }
 
+/// Like [`checksum`], but returns an error for input which isn't
+/// uppercase alphanumeric ASCII instead of panicking.
+///
+/// ```
+/// assert_eq!(luhn::try_checksum(b"US037833100"), Ok(b'5'));
+/// assert!(luhn::try_checksum(b"us037833100").is_err());
+/// ```
+pub fn try_checksum(input: &[u8]) -> Result<u8, LuhnError> {
+    match input
+        .iter()
+        .position(|b| !b.is_ascii_digit() && !b.is_ascii_uppercase())
+    {
+        Some(position) => Err(LuhnError::InvalidCharacter {
+            position,
+            // The input needn't be UTF-8; this is U+FFFD if it isn't.
+            character: String::from_utf8_lossy(&input[position..])
+                .chars()
+                .next()
+                .unwrap(),
+        }),
+        None => Ok(checksum(input)),
+    }
+}
+
+/// Checks that `s` is uppercase alphanumeric ASCII ending in the decimal
+/// check digit [`checksum`] computes, as with ISINs.  This accepts
+/// whatever `checksum` can be appended to.
+///
+/// A [`LuhnError::BadCheckDigit`] says which digit was expected.
+///
+/// ```
+/// use luhn::LuhnError;
+///
+/// assert_eq!(luhn::validate_alphanumeric("US0378331005"), Ok(()));
+/// assert_eq!(
+///     luhn::validate_alphanumeric("US0378331006"),
+///     Err(LuhnError::BadCheckDigit {
+///         position: 11,
+///         expected: '5',
+///         found: '6'
+///     })
+/// );
+/// ```
+pub fn validate_alphanumeric(s: &str) -> Result<(), LuhnError> {
+    let (position, found) = match s.char_indices().next_back() {
+        Some(last) => last,
+        None => return Err(LuhnError::TooShort { len: 0, min: 1 }),
+    };
+    let expected = try_checksum(&s.as_bytes()[..position])? as char;
+    if !found.is_ascii_digit() {
+        return Err(LuhnError::InvalidCharacter {
+            position,
+            character: found,
+        });
+    }
+    if found != expected {
+        return Err(LuhnError::BadCheckDigit {
+            position,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+
+/// Computes the Luhn check digit for `input`, with `value` giving what
+/// each character is worth, for alphabets [`checksum`] doesn't know.
+///
+/// As in [`checksum`], values of ten or more count as their decimal
+/// digits, so a character worth 12 counts as a 1 followed by a 2.  Fails
+/// on the first character `value` returns `None` for, at its byte offset
+/// in the input.  Returns an ASCII digit.
+///
+/// ```
+/// // A legacy scheme where `*` is worth 10 and `#` is worth 11.
+/// let value = |c: char| match c {
+///     '*' => Some(10),
+///     '#' => Some(11),
+///     c => c.to_digit(10).map(|d| d as u8),
+/// };
+/// assert_eq!(luhn::checksum_map("12*#".chars(), value), Ok(luhn::checksum(b"121011")));
+/// assert!(luhn::checksum_map("12?".chars(), value).is_err());
+/// ```
+pub fn checksum_map(
+    input: impl IntoIterator<Item = char>,
+    value: impl Fn(char) -> Option<u8>,
+) -> Result<u8, LuhnError> {
+    // The rightmost digit is doubled, but which that is isn't known until
+    // the end, so keep the sum for both ways of counting.
+    let double = |d: u32| d / 5 + (2 * d) % 10;
+    let mut sums = [0, 0];
+    let mut len = 0;
+    let mut position = 0;
+    for character in input {
+        let v = value(character).ok_or(LuhnError::InvalidCharacter {
+            position,
+            character,
+        })?;
+        position += character.len_utf8();
+        let digits = [v / 100, v / 10 % 10, v % 10];
+        let skip = match v {
+            0..=9 => 2,
+            10..=99 => 1,
+            _ => 0,
+        };
+        for &d in &digits[skip..] {
+            let d = u32::from(d);
+            sums[len % 2] += double(d);
+            sums[1 - len % 2] += d;
+            len += 1;
+        }
+    }
+    // sums[0] doubles the digits at even indices counting from zero.
+    let sum = if len == 0 { 0 } else { sums[(len - 1) % 2] };
+    Ok(((10 - sum % 10) % 10) as u8 + b'0')
+}
+
+/// Makes `s` validate by changing only the digit at byte offset
+/// `free_index`, eg. to embed a payload in an identifier with one
+/// position kept spare.
+///
+/// Changing any one digit reaches every Luhn sum, so there's exactly one
+/// digit which works.  Fails if `s` has anything other than ASCII digits,
+/// or is too short to have a digit at `free_index`.
+///
+/// ```
+/// assert_eq!(luhn::force_valid("4111111011111111", 7).unwrap(), "4111111111111111");
+/// assert_eq!(luhn::force_valid("79927398713", 10).unwrap(), "79927398713");
+/// ```
+pub fn force_valid(s: &str, free_index: usize) -> Result<String, LuhnError> {
+    if let Some((position, character)) = s.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(LuhnError::InvalidCharacter {
+            position,
+            character,
+        });
+    }
+    if free_index >= s.len() {
+        return Err(LuhnError::TooShort {
+            len: s.len(),
+            min: free_index + 1,
+        });
+    }
+    let mut digits = s.as_bytes().to_vec();
+    for d in b'0'..=b'9' {
+        digits[free_index] = d;
+        if valid(std::str::from_utf8(&digits).unwrap()) {
+            break;
+        }
+    }
+    Ok(String::from_utf8(digits).unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod allocations {
+        use std::alloc::{GlobalAlloc, Layout, System};
+        use std::cell::Cell;
+
+        /// Counts allocations on each thread, so that tests running at
+        /// the same time don't disturb each other.
+        struct Counting;
+
+        thread_local! {
+            static COUNT: Cell<usize> = const { Cell::new(0) };
+        }
+
+        unsafe impl GlobalAlloc for Counting {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                COUNT.with(|count| count.set(count.get() + 1));
+                System.alloc(layout)
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                System.dealloc(ptr, layout)
+            }
+        }
+
+        #[global_allocator]
+        static ALLOCATOR: Counting = Counting;
+
+        /// How many times `f` allocates.
+        pub fn count(f: impl FnOnce()) -> usize {
+            let before = COUNT.with(Cell::get);
+            f();
+            COUNT.with(Cell::get) - before
+        }
+    }
+
     #[test]
     fn accepts_4111111111111111() {
         assert!(valid("4111111111111111"));
@@ -169,6 +512,283 @@ mod tests {
         assert!(!validate_isin(*b"CA9861193023")); // Yorbeau Res (two chars transposed)
     }
 
+    #[test]
+    fn checksum_of_long_input() {
+        // This used to overflow.
+        let s = "9".repeat(100);
+        assert_eq!(checksum(s.as_bytes()), b'0');
+    }
+
+    #[test]
+    fn checksum_map_agrees_with_checksum() {
+        let base36 = |c: char| c.to_digit(36).map(|d| d as u8);
+        for s in &["", "0", "7992739871", "US037833100", "BMG491BT108", "Z9Z9Z"] {
+            assert_eq!(
+                checksum_map(s.chars(), base36),
+                Ok(checksum(s.as_bytes())),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            checksum_map("1é2x".chars(), |c| c.to_digit(10).map(|d| d as u8)),
+            Err(LuhnError::InvalidCharacter {
+                position: 1,
+                character: 'é'
+            })
+        );
+        assert_eq!(
+            checksum_map("ab".chars(), |c| Some(c as u8)),
+            Ok(checksum(b"9798"))
+        );
+        assert_eq!(
+            checksum_map("xé".chars(), |c| Some(c as u8)),
+            Ok(checksum(b"120233"))
+        );
+    }
+
+    #[test]
+    fn try_checksum_reports_the_bad_character() {
+        assert_eq!(try_checksum(b""), Ok(b'0'));
+        assert_eq!(try_checksum(b"7992739871"), Ok(b'3'));
+        assert_eq!(
+            try_checksum("12é".as_bytes()),
+            Err(LuhnError::InvalidCharacter {
+                position: 2,
+                character: 'é'
+            })
+        );
+        assert_eq!(
+            try_checksum(b"1\xff"),
+            Err(LuhnError::InvalidCharacter {
+                position: 1,
+                character: '\u{fffd}'
+            })
+        );
+    }
+
+    #[test]
+    fn validate_alphanumeric_agrees_with_checksum() {
+        for s in &["18", "79927398713", "US0378331005", "BMG491BT1088", "0"] {
+            assert_eq!(validate_alphanumeric(s), Ok(()), "{}", s);
+        }
+        assert_eq!(
+            validate_alphanumeric(""),
+            Err(LuhnError::TooShort { len: 0, min: 1 })
+        );
+        assert_eq!(
+            validate_alphanumeric("us0378331005"),
+            Err(LuhnError::InvalidCharacter {
+                position: 0,
+                character: 'u'
+            })
+        );
+        assert_eq!(
+            validate_alphanumeric("US037833100é"),
+            Err(LuhnError::InvalidCharacter {
+                position: 11,
+                character: 'é'
+            })
+        );
+        assert_eq!(
+            validate_alphanumeric("US037833100A"),
+            Err(LuhnError::InvalidCharacter {
+                position: 11,
+                character: 'A'
+            })
+        );
+    }
+
+    #[test]
+    fn force_valid_changes_one_digit() {
+        for free_index in 0..16 {
+            let forced = force_valid("4012888888881882", free_index).unwrap();
+            assert!(valid(&forced), "{}", forced);
+            for (i, (a, b)) in forced.bytes().zip("4012888888881882".bytes()).enumerate() {
+                assert!(i == free_index || a == b, "{}", forced);
+            }
+        }
+        assert_eq!(
+            force_valid("?9927398713", 0),
+            Err(LuhnError::InvalidCharacter {
+                position: 0,
+                character: '?'
+            })
+        );
+        assert_eq!(force_valid("09927398713", 0).unwrap(), "79927398713");
+        assert_eq!(
+            force_valid("7992", 4),
+            Err(LuhnError::TooShort { len: 4, min: 5 })
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn masked_prefix_hides_all_but_six() {
+        assert_eq!(masked_prefix("4111111111111111"), "411111**********");
+        assert_eq!(masked_prefix("4111"), "4111");
+    }
+
+    #[test]
+    fn small_inputs_dont_allocate() {
+        use std::hint::black_box;
+
+        let inputs = [
+            "",
+            "0",
+            "4111111111111111",
+            "4111 1111 1111 1111",
+            "US0378331005",
+            "490154203237518",
+            "1F3A9C",
+            "12345678901234567890123456789012",
+            // Valid for each of the schemes.
+            "4006381333931",
+            "106141412345678908",
+            "1Z999AA10123456784",
+            "9205590164917312751089",
+            "12345675",
+            "91 80 6101 004-0",
+            "1M8GDM9AXKP042788",
+            "01 801 100 12 0002 3305 967",
+            "1234567893",
+            "2123 45670 1",
+            "123.456.789-09",
+            "11.222.333/0001-81",
+            "44051401458",
+            "10000000146",
+            "X1234567L",
+            "01010750160",
+            "69435151530",
+            "12 34561",
+            "21 00000 00003 13947 14300 09017",
+            "4111-1111-1111-1111",
+            "47992739871",
+            "HAPP-YBDA-Y27H",
+        ];
+        // The crate's own validators which are built rather than built in.
+        let luhn_spec = |check_position| {
+            spec::SchemeSpec {
+                name: format!("spec {:?}", check_position),
+                alphabet: "0123456789".to_string(),
+                check_alphabet: None,
+                weights: vec![2, 1],
+                modulus: 10,
+                sum_product_digits: true,
+                check_position,
+                min_len: 2,
+                max_len: None,
+            }
+            .build()
+            .unwrap()
+        };
+        let built: Vec<Box<dyn Validator>> = vec![
+            Box::new(luhn_spec(spec::CheckPosition::Last)),
+            Box::new(luhn_spec(spec::CheckPosition::First)),
+            Box::new(pattern::Pattern::new("####-####-####-###C").unwrap()),
+            Box::new(pattern::Pattern::new("US?????????C").unwrap()),
+            Box::new(voucher::VoucherFormat::new("XXXX-XXXX-XXXC").unwrap()),
+            Box::new(
+                rules::length(12..=19)
+                    .and(rules::prefix_in(&["4"]).or(rules::prefix_range(51..=55)))
+                    .and(rules::luhn())
+                    .named("rules"),
+            ),
+        ];
+        let validators: Vec<&dyn Validator> = schemes()
+            .map(|scheme| scheme as &dyn Validator)
+            .chain(built.iter().map(|v| &**v))
+            .collect();
+        let algorithms = [
+            Algorithm::Luhn,
+            Algorithm::Verhoeff,
+            Algorithm::Damm,
+            Algorithm::Gs1,
+            Algorithm::Iso7064Mod11_10,
+            Algorithm::Iso7064Mod97_10,
+        ];
+        for &input in &inputs {
+            let upper = input.to_ascii_uppercase();
+            let none = |name: &str, f: &dyn Fn()| {
+                assert_eq!(allocations::count(f), 0, "{} on {:?}", name, input);
+            };
+            none("valid", &|| {
+                black_box(valid(input));
+            });
+            if upper.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                none("checksum", &|| {
+                    black_box(checksum(upper.as_bytes()));
+                });
+            }
+            none("checksum_map", &|| {
+                black_box(checksum_map(input.chars(), |c| {
+                    c.to_digit(36).map(|d| d as u8)
+                }))
+                .ok();
+            });
+            none("validity", &|| {
+                black_box(validity(input));
+            });
+            none("validate_with", &|| {
+                black_box(validate_with(input, &ValidateConfig::PAN)).ok();
+            });
+            none("valid_leading_check", &|| {
+                black_box(valid_leading_check(input));
+            });
+            none("Confusables::valid", &|| {
+                black_box(Confusables::ALL.valid(input));
+            });
+            none("Alphabet::valid", &|| {
+                black_box(mod_n::Alphabet::HEX.valid(input));
+            });
+            none("Alphabet::checksum", &|| {
+                black_box(mod_n::Alphabet::BASE36.checksum(input)).ok();
+            });
+            none("valid_many", &|| {
+                valid_many(input.as_bytes(), &[0..input.len(), 0..0], &mut [false; 2]);
+            });
+            for algorithm in &algorithms {
+                none(&format!("{:?}", algorithm), &|| {
+                    black_box(algorithm.validate(input));
+                });
+            }
+            for scheme in &validators {
+                none(scheme.name(), &|| {
+                    black_box(scheme.validate(input));
+                });
+            }
+        }
+        // Each scheme's success path is covered too.
+        for scheme in &validators {
+            assert!(
+                inputs.iter().any(|input| scheme.validate(input)),
+                "{}",
+                scheme.name()
+            );
+        }
+    }
+
+    #[test]
+    fn check_digit_helpers_only_allocate_their_result() {
+        let once = |name: &str, f: &dyn Fn()| {
+            assert_eq!(allocations::count(f), 1, "{}", name);
+        };
+        once("prepend_checksum", &|| {
+            prepend_checksum("7992739871").unwrap();
+        });
+        once("force_valid", &|| {
+            force_valid("4111111011111111", 7).unwrap();
+        });
+        once("Alphabet::append", &|| {
+            mod_n::Alphabet::HEX.append("1F3A9").unwrap();
+        });
+        once("Algorithm::compute", &|| {
+            Algorithm::Iso7064Mod97_10
+                .compute("3214282912345698765432161182")
+                .unwrap();
+        });
+    }
+
     #[test]
     fn readme() {
         // A string which doesn't validate
@@ -0,0 +1,349 @@
+//! Validating a file of identifiers, one per line.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+
+use crate::error::ErrorKind;
+use crate::{Monitor, Progress};
+
+/// A summary of the lines checked by [`validate_lines`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineReport {
+    /// The number of lines read.
+    pub lines: u64,
+    /// The number of lines which were valid.
+    pub valid: u64,
+    /// The number of lines which weren't.
+    pub invalid: u64,
+    /// The line numbers, counting from 1, of the first
+    /// [`MAX_FAILED_LINES`](Self::MAX_FAILED_LINES) invalid lines.
+    pub failed_lines: Vec<u64>,
+    /// How many lines failed for each reason.
+    pub reasons: BTreeMap<ErrorKind, u64>,
+    /// Whether validation was cancelled through a [`Monitor`], so that the
+    /// report only covers the lines before that point.
+    pub cancelled: bool,
+}
+
+impl LineReport {
+    /// The most line numbers `failed_lines` holds.
+    pub const MAX_FAILED_LINES: usize = 100;
+
+    /// Adds `later`, a report on the lines following these, to this one,
+    /// renumbering its failed lines to follow on.
+    pub fn merge(&mut self, later: LineReport) {
+        // Callers can push past the cap themselves, leaving no room.
+        let room = LineReport::MAX_FAILED_LINES.saturating_sub(self.failed_lines.len());
+        let lines = self.lines;
+        self.failed_lines
+            .extend(later.failed_lines.into_iter().take(room).map(|n| lines + n));
+        self.lines += later.lines;
+        self.valid += later.valid;
+        self.invalid += later.invalid;
+        for (kind, count) in later.reasons {
+            *self.reasons.entry(kind).or_insert(0) += count;
+        }
+        self.cancelled |= later.cancelled;
+    }
+}
+
+/// Validates each line read from `r`.
+///
+/// A line is valid if it's all ASCII digits, ending in a good check
+/// digit; trailing `\r`s are ignored, and so is a final newline.  Lines
+/// which aren't UTF-8 count as having an invalid character.
+///
+/// ```
+/// use luhn::ErrorKind;
+///
+/// let dump = "4111111111111111\n4111111111111112\n\n79927398713\n";
+/// let report = luhn::validate_lines(dump.as_bytes()).unwrap();
+/// assert_eq!((report.valid, report.invalid), (2, 2));
+/// assert_eq!(report.failed_lines, [2, 3]);
+/// assert_eq!(report.reasons[&ErrorKind::BadCheckDigit], 1);
+/// ```
+pub fn validate_lines(r: impl BufRead) -> io::Result<LineReport> {
+    validate_lines_monitored(r, &Monitor::new())
+}
+
+/// Like [`validate_lines`], but tells `monitor` how it's getting on.
+/// Progress counts invalid lines as matches.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn validate_lines_monitored(mut r: impl BufRead, monitor: &Monitor) -> io::Result<LineReport> {
+    let mut report = LineReport::default();
+    let mut line = Vec::new();
+    let mut bytes = 0;
+    let mut next_check = 0;
+    loop {
+        if bytes >= next_check {
+            if monitor.cancelled() {
+                report.cancelled = true;
+                break;
+            }
+            if bytes > 0 {
+                monitor.report(Progress {
+                    bytes,
+                    matches: report.invalid,
+                });
+            }
+            next_check = bytes + Monitor::INTERVAL as u64;
+        }
+        line.clear();
+        let n = r.read_until(b'\n', &mut line)?;
+        if n == 0 {
+            break;
+        }
+        bytes += n as u64;
+        report.lines += 1;
+        while matches!(line.last(), Some(b'\n' | b'\r')) {
+            line.pop();
+        }
+        match crate::check_digits(&String::from_utf8_lossy(&line)) {
+            Ok(()) => report.valid += 1,
+            Err(err) => {
+                report.invalid += 1;
+                if report.failed_lines.len() < LineReport::MAX_FAILED_LINES {
+                    report.failed_lines.push(report.lines);
+                }
+                #[cfg(feature = "tracing")]
+                tracing::trace!(line = report.lines, reason = %err.kind(), "invalid line");
+                *report.reasons.entry(err.kind()).or_insert(0) += 1;
+            }
+        }
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        lines = report.lines,
+        valid = report.valid,
+        invalid = report.invalid,
+        "validated lines"
+    );
+    monitor.report(Progress {
+        bytes,
+        matches: report.invalid,
+    });
+    Ok(report)
+}
+
+/// Like [`validate_lines`], but for lines already in memory, which are
+/// split into chunks validated on rayon's thread pool.  The result is the
+/// same.
+#[cfg(feature = "parallel")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn validate_lines_parallel(bytes: &[u8]) -> LineReport {
+    validate_lines_in_chunks(bytes, Monitor::INTERVAL, &Monitor::new())
+}
+
+/// Like [`validate_lines_parallel`], but tells `monitor` how it's getting
+/// on, from rayon's threads.
+///
+/// If cancelled, the report covers the lines up to the first chunk which
+/// was skipped.
+///
+/// ```
+/// use luhn::{CancelToken, Monitor};
+///
+/// let token = CancelToken::new();
+/// let cancel = |_| token.cancel();
+/// let monitor = Monitor::new().on_progress(&cancel).cancel_on(&token);
+/// // A chunk per line, and more chunks than threads, so that some haven't
+/// // started when the first one finishes and cancels the job.
+/// let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+/// let dump = format!("{}\n", "4".repeat(Monitor::INTERVAL)).repeat(threads + 1);
+/// let report = luhn::validate_lines_parallel_monitored(dump.as_bytes(), &monitor);
+/// assert!(report.cancelled);
+/// assert!(report.lines <= threads as u64);
+/// ```
+#[cfg(feature = "parallel")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn validate_lines_parallel_monitored(bytes: &[u8], monitor: &Monitor) -> LineReport {
+    validate_lines_in_chunks(bytes, Monitor::INTERVAL, monitor)
+}
+
+#[cfg(feature = "parallel")]
+pub(crate) fn validate_lines_in_chunks(
+    bytes: &[u8],
+    chunk_size: usize,
+    monitor: &Monitor,
+) -> LineReport {
+    use rayon::prelude::*;
+
+    use crate::monitor::Totals;
+
+    let totals = Totals::default();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        let end = match bytes[(start + chunk_size).min(bytes.len())..]
+            .iter()
+            .position(|&b| b == b'\n')
+        {
+            Some(i) => (start + chunk_size).min(bytes.len()) + i + 1,
+            None => bytes.len(),
+        };
+        chunks.push(&bytes[start..end]);
+        start = end;
+    }
+    let reports: Vec<Option<LineReport>> = chunks
+        .into_par_iter()
+        .map(|chunk| {
+            if monitor.cancelled() {
+                return None;
+            }
+            // Reading from a slice can't fail.
+            let report = validate_lines(chunk).unwrap();
+            totals.add(
+                Progress {
+                    bytes: chunk.len() as u64,
+                    matches: report.invalid,
+                },
+                monitor,
+            );
+            Some(report)
+        })
+        .collect();
+    // Chunks after one which was skipped can't be numbered, so a cancelled
+    // report covers the chunks up to there.
+    let mut report = LineReport::default();
+    for later in reports {
+        match later {
+            Some(later) => report.merge(later),
+            None => {
+                report.cancelled = true;
+                break;
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CancelToken;
+
+    #[test]
+    fn counts_reasons() {
+        let dump: &[u8] = b"4111111111111111\r\n41111111 11111111\n\xff\n\n4111111111111112";
+        let report = validate_lines(dump).unwrap();
+        assert_eq!(report.lines, 5);
+        assert_eq!(report.valid, 1);
+        assert_eq!(report.failed_lines, [2, 3, 4, 5]);
+        assert_eq!(
+            report.reasons.into_iter().collect::<Vec<_>>(),
+            [
+                (ErrorKind::InvalidCharacter, 2),
+                (ErrorKind::TooShort, 1),
+                (ErrorKind::BadCheckDigit, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn caps_failed_lines() {
+        let dump = "1\n".repeat(150);
+        let report = validate_lines(dump.as_bytes()).unwrap();
+        assert_eq!(report.invalid, 150);
+        assert_eq!(report.failed_lines.len(), LineReport::MAX_FAILED_LINES);
+        assert_eq!(report.failed_lines.last(), Some(&100));
+    }
+
+    #[test]
+    fn merges_reports() {
+        let mut report = validate_lines(&b"18\n19\n"[..]).unwrap();
+        report.merge(validate_lines(&b"1\n18\n17\n"[..]).unwrap());
+        assert_eq!(report, validate_lines(&b"18\n19\n1\n18\n17\n"[..]).unwrap());
+        assert_eq!(report.failed_lines, [2, 3, 5]);
+
+        let mut report = validate_lines("1\n".repeat(99).as_bytes()).unwrap();
+        report.merge(validate_lines(&b"1\n1\n"[..]).unwrap());
+        assert_eq!(report.failed_lines.len(), LineReport::MAX_FAILED_LINES);
+        assert_eq!(report.invalid, 101);
+
+        report.failed_lines.push(102);
+        report.merge(validate_lines(&b"1\n"[..]).unwrap());
+        assert_eq!(report.failed_lines.len(), LineReport::MAX_FAILED_LINES + 1);
+        assert_eq!(report.invalid, 102);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_validate_lines() {
+        for dump in [
+            &b"4111111111111111\r\n41111111 11111111\n\xff\n\n4111111111111112"[..],
+            b"18\n\n\n19\n",
+            b"",
+            b"\n",
+        ]
+        .iter()
+        {
+            let expected = validate_lines(*dump).unwrap();
+            assert_eq!(validate_lines_parallel(dump), expected);
+            for chunk_size in 1..=dump.len() {
+                assert_eq!(
+                    validate_lines_in_chunks(dump, chunk_size, &Monitor::new()),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reports_progress() {
+        use std::sync::Mutex;
+
+        let dump = format!("{}\n1\n", "4".repeat(Monitor::INTERVAL)).repeat(2);
+        let reports = Mutex::new(Vec::new());
+        let record = |progress| reports.lock().unwrap().push(progress);
+        let monitor = Monitor::new().on_progress(&record);
+        let report = validate_lines_monitored(dump.as_bytes(), &monitor).unwrap();
+        assert_eq!(report.invalid, 4);
+        let line = Monitor::INTERVAL as u64 + 1;
+        assert_eq!(
+            reports.into_inner().unwrap(),
+            [
+                Progress {
+                    bytes: line,
+                    matches: 1
+                },
+                Progress {
+                    bytes: 2 * line + 2,
+                    matches: 3
+                },
+                Progress {
+                    bytes: 2 * line + 4,
+                    matches: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let token = CancelToken::new();
+        let monitor = Monitor::new().cancel_on(&token);
+        let report = validate_lines_monitored(&b"18\n"[..], &monitor).unwrap();
+        assert!(!report.cancelled);
+        assert_eq!(report.valid, 1);
+
+        // Cancelled from the first progress report, and noticed at the
+        // next check.
+        let cancel = |_| token.cancel();
+        let monitor = Monitor::new().on_progress(&cancel).cancel_on(&token);
+        let dump = format!("{}\n", "4".repeat(Monitor::INTERVAL)).repeat(3);
+        let report = validate_lines_monitored(dump.as_bytes(), &monitor).unwrap();
+        assert!(report.cancelled);
+        assert_eq!(report.lines, 2);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_stops_when_cancelled() {
+        let token = CancelToken::new();
+        token.cancel();
+        let monitor = Monitor::new().cancel_on(&token);
+        let report = validate_lines_in_chunks(b"18\n18\n", 3, &monitor);
+        assert!(report.cancelled);
+        assert_eq!(report.lines, 0);
+    }
+}
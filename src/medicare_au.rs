@@ -0,0 +1,82 @@
+//! Australian Medicare card numbers.
+//!
+//! A card number is ten digits: eight identifying the card, starting
+//! with 2 to 6, a check digit weighting those by 1, 3, 7, 9, 1, 3, 7, 9,
+//! and an issue number.  Each person on the card also has an individual
+//! reference number (IRN), from 1 to 9, often written as an eleventh
+//! digit.
+//!
+//! ```
+//! use luhn::medicare_au;
+//!
+//! assert!(medicare_au::valid("2123 45670 1"));
+//! assert!(medicare_au::valid("21234567012"));
+//! assert_eq!(medicare_au::irn("21234567012"), Some(2));
+//! ```
+
+const WEIGHTS: [u32; 8] = [1, 3, 7, 9, 1, 3, 7, 9];
+const LEN: usize = 10;
+
+/// Whether `s` is a card number, with or without an IRN after it and
+/// optionally grouped with spaces, whose check digit is good.
+pub fn valid(s: &str) -> bool {
+    digits(s).is_some()
+}
+
+/// The IRN from a valid card number followed by one.  `None` if `s` has
+/// no IRN or isn't [`valid`].
+pub fn irn(s: &str) -> Option<u8> {
+    let (digits, len) = digits(s)?;
+    (len > LEN).then(|| digits[LEN])
+}
+
+/// The digits of `s` as numbers, and how many there are, if it's valid.
+fn digits(s: &str) -> Option<([u8; LEN + 1], usize)> {
+    let mut digits = [0; LEN + 1];
+    let mut len = 0;
+    for c in s.chars().filter(|&c| c != ' ') {
+        let d = c.to_digit(10)?;
+        *digits.get_mut(len)? = d as u8;
+        len += 1;
+    }
+    let irn_ok = match len {
+        LEN => true,
+        n if n == LEN + 1 => digits[LEN] != 0,
+        _ => false,
+    };
+    if !irn_ok || !(2..=6).contains(&digits[0]) {
+        return None;
+    }
+    let sum: u32 = digits
+        .iter()
+        .zip(WEIGHTS.iter())
+        .map(|(&d, w)| u32::from(d) * w)
+        .sum();
+    (sum % 10 == u32::from(digits[8])).then_some((digits, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_card_numbers() {
+        assert!(valid("2123456701"));
+        assert!(valid("2123 45670 1"));
+        assert!(!valid("2123456711"));
+        assert!(!valid("212345670"));
+        assert!(!valid("212345670a"));
+        // The check digit is right, but cards don't start with 1.
+        assert!(!valid("1123456791"));
+    }
+
+    #[test]
+    fn handles_irns() {
+        assert_eq!(irn("21234567019"), Some(9));
+        assert_eq!(irn("2123 45670 1 3"), Some(3));
+        assert_eq!(irn("2123456701"), None);
+        assert!(!valid("21234567010"));
+        assert!(!valid("212345670112"));
+        assert_eq!(irn("21234567112"), None);
+    }
+}
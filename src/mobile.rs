@@ -0,0 +1,65 @@
+//! Kotlin and Swift bindings, via UniFFI.
+//!
+//! Build the shared library with
+//! `cargo rustc --release --lib --crate-type cdylib --features uniffi`,
+//! then generate bindings from it with `uniffi-bindgen`, eg.
+//! `uniffi-bindgen generate --library target/release/libluhn.so --language kotlin --out-dir out`.
+//!
+//! A `Pan` crosses the boundary as a string, and is validated on the way
+//! into Rust.
+
+use crate::{CardNetwork, LuhnError, Pan};
+
+uniffi::custom_type!(Pan, String, {
+    lower: |pan| pan.0,
+    try_lift: |s| Ok(Pan::new(&s)?),
+});
+
+/// Validates the given string of ASCII digits using the Luhn algorithm.
+/// Empty input, or anything other than digits, is invalid.
+#[uniffi::export]
+fn valid(s: String) -> bool {
+    crate::check_digits(&s).is_ok()
+}
+
+/// Computes the check digit for the given uppercase alphanumeric string.
+#[uniffi::export]
+fn checksum(s: String) -> Result<String, LuhnError> {
+    Ok((crate::try_checksum(s.as_bytes())? as char).to_string())
+}
+
+/// Validates `number` as a card number.
+#[uniffi::export]
+fn parse_pan(number: String) -> Result<Pan, LuhnError> {
+    Pan::new(&number)
+}
+
+/// The network which issued the card, if we recognise it.
+#[uniffi::export]
+fn pan_network(pan: Pan) -> Option<CardNetwork> {
+    pan.network()
+}
+
+/// Guesses the card network from the leading digits of a number.
+#[uniffi::export]
+fn detect_network(number: String) -> Option<CardNetwork> {
+    CardNetwork::detect(&number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_work() {
+        assert!(valid("4111111111111111".into()));
+        assert!(!valid("".into()));
+        assert!(!valid("4111 1111 1111 1111".into()));
+        assert!(!valid("abc".into()));
+        assert_eq!(checksum("US037833100".into()), Ok("5".to_string()));
+        assert!(checksum("us037833100".into()).is_err());
+        let pan = parse_pan("378282246310005".into()).unwrap();
+        assert_eq!(pan_network(pan), Some(CardNetwork::AmericanExpress));
+        assert_eq!(detect_network("4".into()), Some(CardNetwork::Visa));
+    }
+}
@@ -0,0 +1,367 @@
+//! Luhn mod N, which generalises the Luhn algorithm to alphabets of any
+//! size, so that the check character is drawn from the same alphabet as
+//! the rest of the input.
+//!
+//! An [`Alphabet`] is a list of characters, each standing for its
+//! position in the list.  Presets cover hexadecimal, Crockford's base 32,
+//! base 36, base 62 and [voucher codes](crate::voucher), or you can bring
+//! your own:
+//!
+//! ```
+//! use luhn::mod_n::Alphabet;
+//!
+//! assert_eq!(Alphabet::BASE36.checksum("LUHN"), Ok('Y'));
+//! assert_eq!(Alphabet::BASE36.append("LUHN").unwrap(), "LUHNY");
+//! assert!(Alphabet::BASE36.valid("LUHNY"));
+//!
+//! let dna = Alphabet::new("ACGT");
+//! assert!(dna.valid(&dna.append("GATTACA").unwrap()));
+//! ```
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+use crate::LuhnError;
+
+/// The characters a Luhn mod N scheme works over.
+#[derive(Debug, Clone)]
+pub struct Alphabet {
+    /// The characters written out, each standing for its index.
+    symbols: Cow<'static, [u8]>,
+    /// Maps an input character onto the symbol it stands for, eg. to
+    /// accept lowercase letters.
+    fold: fn(u8) -> u8,
+}
+
+impl Alphabet {
+    /// `0-9A-F`, accepting lowercase letters too.
+    pub const HEX: Alphabet = Alphabet {
+        symbols: Cow::Borrowed(b"0123456789ABCDEF"),
+        fold: |c| c.to_ascii_uppercase(),
+    };
+
+    /// Crockford's base 32, `0-9A-Z` without `I`, `L`, `O` and `U`.
+    /// Lowercase letters are accepted, and the ambiguous letters fold onto
+    /// the digits they look like: `O` to `0`, and `I` and `L` to `1`.
+    ///
+    /// This is an alternative to the mod 37 check symbol Crockford
+    /// suggests, which needs five extra symbols; here the check symbol is
+    /// an ordinary base 32 character.
+    pub const CROCKFORD: Alphabet = Alphabet {
+        symbols: Cow::Borrowed(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ"),
+        fold: |c| match c.to_ascii_uppercase() {
+            b'O' => b'0',
+            b'I' | b'L' => b'1',
+            c => c,
+        },
+    };
+
+    /// `0-9A-Z`, accepting lowercase letters too.
+    pub const BASE36: Alphabet = Alphabet {
+        symbols: Cow::Borrowed(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        fold: |c| c.to_ascii_uppercase(),
+    };
+
+    /// `0-9A-Za-z`, case-sensitively.
+    pub const BASE62: Alphabet = Alphabet {
+        symbols: Cow::Borrowed(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz"),
+        fold: |c| c,
+    };
+
+    /// `2-9A-Z` without `I` and `O`, for codes people read and type, such
+    /// as [vouchers](crate::voucher): there's no `0`, `O`, `1` or `I` to
+    /// mix up.  Lowercase letters are accepted.
+    pub const VOUCHER: Alphabet = Alphabet {
+        symbols: Cow::Borrowed(b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ"),
+        fold: |c| c.to_ascii_uppercase(),
+    };
+
+    /// An alphabet of the given characters, which are matched exactly.
+    ///
+    /// Panics unless `symbols` is at least two ASCII characters, none of
+    /// them repeated.
+    pub fn new(symbols: impl Into<Cow<'static, str>>) -> Alphabet {
+        let symbols = match symbols.into() {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        };
+        assert!(
+            symbols.len() >= 2
+                && symbols.is_ascii()
+                && symbols
+                    .iter()
+                    .enumerate()
+                    .all(|(i, c)| !symbols[..i].contains(c)),
+            "Not a usable alphabet: {:?}",
+            String::from_utf8_lossy(&symbols)
+        );
+        Alphabet {
+            symbols,
+            fold: |c| c,
+        }
+    }
+
+    /// N, the number of characters.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// The characters, in order of value.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.symbols).unwrap()
+    }
+
+    /// Computes the check character for `body`.
+    pub fn checksum(&self, body: &str) -> Result<char, LuhnError> {
+        let n = self.len() as u32;
+        let sum = self.sum(body, true)?;
+        Ok(self.symbols[((n - sum % n) % n) as usize] as char)
+    }
+
+    /// `body` with its check character appended.
+    pub fn append(&self, body: &str) -> Result<String, LuhnError> {
+        let check = self.checksum(body)?;
+        let mut s = String::with_capacity(body.len() + 1);
+        s.push_str(body);
+        s.push(check);
+        Ok(s)
+    }
+
+    /// Whether `s` is non-empty, within the alphabet, and ends in a good
+    /// check character.
+    pub fn valid(&self, s: &str) -> bool {
+        !s.is_empty()
+            && self
+                .sum(s, false)
+                .is_ok_and(|sum| sum % self.len() as u32 == 0)
+    }
+
+    /// What `c` stands for, if it's in the alphabet.
+    fn value(&self, c: char) -> Option<u32> {
+        let c = (self.fold)(u8::try_from(c).ok()?);
+        self.symbols
+            .iter()
+            .position(|&symbol| symbol == c)
+            .map(|i| i as u32)
+    }
+
+    /// The Luhn mod N sum of `s`, doubling every second character from the
+    /// right, starting with the last if `double_last`.
+    fn sum(&self, s: &str, double_last: bool) -> Result<u32, LuhnError> {
+        let n = self.len() as u32;
+        let mut double = double_last;
+        let mut sum = 0;
+        for (position, character) in s.char_indices().rev() {
+            let value = self.value(character).ok_or(LuhnError::InvalidCharacter {
+                position,
+                character,
+            })?;
+            let addend = if double { value * 2 } else { value };
+            sum += addend / n + addend % n;
+            double = !double;
+        }
+        Ok(sum)
+    }
+}
+
+/// Computes the check character for a hexadecimal string, using Luhn mod
+/// 16.  A shorthand for [`Alphabet::HEX`].
+///
+/// Input must be ASCII hex digits, in either case; panics otherwise.
+/// Returns a hex digit, uppercase if it's a letter.
+///
+/// ```
+/// let mut id = "1F3A9".to_string();
+/// id.push(luhn::checksum_hex(&id));
+/// assert_eq!(id, "1F3A9C");
+/// assert!(luhn::valid_hex(&id));
+/// ```
+pub fn checksum_hex(input: &str) -> char {
+    checksum_or_panic(&Alphabet::HEX, input, "hexadecimal")
+}
+
+/// Validates a hexadecimal string, in either case, whose last character
+/// is a Luhn mod 16 check character.
+pub fn valid_hex(s: &str) -> bool {
+    Alphabet::HEX.valid(s)
+}
+
+/// Computes the check symbol for a Crockford base 32 string, using Luhn
+/// mod 32.  A shorthand for [`Alphabet::CROCKFORD`].
+///
+/// Input must be Crockford base 32, in either case, and may use `O`, `I`
+/// and `L`, which count as `0`, `1` and `1`; panics otherwise.  Returns an
+/// uppercase symbol.
+///
+/// ```
+/// let mut id = "ORDER42".to_string();
+/// id.push(luhn::checksum_crockford(&id));
+/// assert!(luhn::valid_crockford(&id));
+/// // A reader who mistakes the 0 for an O still gets a valid ID.
+/// assert!(luhn::valid_crockford(&id.replace('O', "0")));
+/// ```
+pub fn checksum_crockford(input: &str) -> char {
+    checksum_or_panic(&Alphabet::CROCKFORD, input, "Crockford base 32")
+}
+
+/// Validates a Crockford base 32 string, folding case and ambiguous
+/// letters as [`checksum_crockford`] does, whose last character is a
+/// Luhn mod 32 check symbol.
+pub fn valid_crockford(s: &str) -> bool {
+    Alphabet::CROCKFORD.valid(s)
+}
+
+/// Computes the check character for a base 62 string, such as a short
+/// link or API key, using Luhn mod 62.  A shorthand for
+/// [`Alphabet::BASE62`].
+///
+/// Input must be ASCII letters and digits; panics otherwise.  Case
+/// matters: `a` and `A` are different characters.
+///
+/// ```
+/// let mut key = "k3Yz9Q".to_string();
+/// key.push(luhn::checksum_base62(&key));
+/// assert!(luhn::valid_base62(&key));
+/// assert!(!luhn::valid_base62(&key[1..]));
+/// ```
+pub fn checksum_base62(input: &str) -> char {
+    checksum_or_panic(&Alphabet::BASE62, input, "base 62")
+}
+
+/// Validates a base 62 string whose last character is a Luhn mod 62
+/// check character.
+pub fn valid_base62(s: &str) -> bool {
+    Alphabet::BASE62.valid(s)
+}
+
+fn checksum_or_panic(alphabet: &Alphabet, input: &str, name: &str) -> char {
+    alphabet
+        .checksum(input)
+        .unwrap_or_else(|_| panic!("Not {}: {:?}", name, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod_10_is_luhn() {
+        let decimal = Alphabet::new("0123456789");
+        for n in 0..2000u32 {
+            let body = n.to_string();
+            assert_eq!(
+                decimal.checksum(&body),
+                Ok(crate::checksum(body.as_bytes()) as char)
+            );
+            assert_eq!(decimal.valid(&body), crate::check_digits(&body).is_ok());
+        }
+    }
+
+    #[test]
+    fn check_characters_can_be_letters() {
+        assert_eq!(Alphabet::BASE36.checksum("LUHN"), Ok('Y'));
+        assert_eq!(Alphabet::BASE36.checksum("luhn"), Ok('Y'));
+        assert_eq!(Alphabet::BASE36.append("LUHN").as_deref(), Ok("LUHNY"));
+        assert!(Alphabet::BASE36.valid("luhny"));
+        assert_eq!(
+            Alphabet::BASE36.checksum("LU-HN"),
+            Err(LuhnError::InvalidCharacter {
+                position: 2,
+                character: '-'
+            })
+        );
+        assert!(!Alphabet::BASE36.valid("LUHNé"));
+    }
+
+    #[test]
+    fn custom_alphabets() {
+        let binary = Alphabet::new(String::from("01"));
+        assert_eq!(binary.len(), 2);
+        let s = binary.append("1101").unwrap();
+        assert!(binary.valid(&s));
+        assert!(!binary.valid("1102"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn alphabets_must_not_repeat() {
+        Alphabet::new("ABCA");
+    }
+
+    #[test]
+    fn hex_check_characters() {
+        assert_eq!(checksum_hex("1F3A9"), 'C');
+        assert_eq!(checksum_hex("1f3a9"), 'C');
+        assert_eq!(checksum_hex(""), '0');
+        assert!(valid_hex("1F3A9C"));
+        assert!(valid_hex("1f3a9c"));
+        assert!(!valid_hex("1F3A9B"));
+        assert!(!valid_hex("1F3G9C"));
+        assert!(!valid_hex(""));
+    }
+
+    #[test]
+    fn hex_catches_single_character_errors() {
+        let mut id = "DEADBEEF".to_string();
+        id.push(checksum_hex(&id));
+        for i in 0..id.len() {
+            for c in "0123456789ABCDEF".chars() {
+                if id[i..].starts_with(c) {
+                    continue;
+                }
+                let mut bad = id.clone();
+                bad.replace_range(i..i + 1, c.encode_utf8(&mut [0; 4]));
+                assert!(!valid_hex(&bad), "{}", bad);
+            }
+        }
+    }
+
+    #[test]
+    fn crockford_symbols() {
+        let crockford = &Alphabet::CROCKFORD;
+        for (i, c) in crockford.symbols.iter().enumerate() {
+            assert_eq!(crockford.value(*c as char), Some(i as u32));
+        }
+        assert_eq!(crockford.value('U'), None);
+    }
+
+    #[test]
+    fn voucher_symbols() {
+        let voucher = &Alphabet::VOUCHER;
+        assert_eq!(voucher.len(), 32);
+        assert!(!voucher.as_str().contains(['0', 'O', '1', 'I']));
+        assert_eq!(voucher.value('z'), Some(31));
+    }
+
+    #[test]
+    fn crockford_folds_ambiguous_letters() {
+        let mut id = "10ABX".to_string();
+        id.push(checksum_crockford(&id));
+        assert!(valid_crockford(&id));
+        assert!(valid_crockford(&id.to_lowercase()));
+        assert!(valid_crockford(
+            &id.replacen('1', "I", 1).replacen('0', "O", 1)
+        ));
+        assert!(valid_crockford(&id.replacen('1', "l", 1)));
+        assert!(!valid_crockford(&id.replacen('X', "Y", 1)));
+        assert!(!valid_crockford(&id.replacen('X', "U", 1)));
+        assert_eq!(checksum_crockford("OIL"), checksum_crockford("011"));
+    }
+
+    #[test]
+    fn base62_is_case_sensitive() {
+        let mut key = "shortLink".to_string();
+        key.push(checksum_base62(&key));
+        assert!(valid_base62(&key));
+        assert!(!valid_base62(&key.replacen('L', "l", 1)));
+        assert!(!valid_base62(&key[..key.len() - 2]));
+        assert!(!valid_base62("short-link"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn checksum_hex_rejects_other_characters() {
+        checksum_hex("12G");
+    }
+}
@@ -0,0 +1,132 @@
+//! Watching long-running validation jobs.
+
+use std::fmt;
+#[cfg(feature = "parallel")]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// How far a job has got, as passed to a [`Monitor`]'s callback.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of bytes looked at so far.
+    pub bytes: u64,
+    /// What's been found so far: numbers when scanning text, and invalid
+    /// lines when validating line by line.
+    pub matches: u64,
+}
+
+/// Hooks for watching a long job, such as
+/// [`validate_lines_monitored`](crate::validate_lines_monitored).
+///
+/// ```
+/// use luhn::{Monitor, Progress};
+/// use std::sync::Mutex;
+///
+/// let last = Mutex::new(Progress::default());
+/// let record = |progress| *last.lock().unwrap() = progress;
+/// let monitor = Monitor::new().on_progress(&record);
+/// let dump = "4111111111111111\n4111111111111112\n";
+/// luhn::validate_lines_monitored(dump.as_bytes(), &monitor).unwrap();
+/// assert_eq!(*last.lock().unwrap(), Progress { bytes: 34, matches: 1 });
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Monitor<'a> {
+    progress: Option<&'a (dyn Fn(Progress) + Sync)>,
+    cancel: Option<&'a CancelToken>,
+}
+
+impl<'a> Monitor<'a> {
+    /// How many bytes a job gets through between progress reports, give
+    /// or take a line or a run of digits.
+    pub const INTERVAL: usize = 1 << 20;
+
+    /// A monitor which does nothing.
+    pub fn new() -> Self {
+        Monitor::default()
+    }
+
+    /// Calls `progress` every [`INTERVAL`](Self::INTERVAL) bytes or so, and
+    /// when the job finishes.  Parallel jobs call it from their worker
+    /// threads, with a running total.
+    pub fn on_progress(mut self, progress: &'a (dyn Fn(Progress) + Sync)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Stops the job early once `token` is cancelled, returning what it
+    /// found in the input up to that point.  The token is checked every
+    /// [`INTERVAL`](Self::INTERVAL) bytes or so.
+    pub fn cancel_on(mut self, token: &'a CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    pub(crate) fn cancelled(&self) -> bool {
+        self.cancel.is_some_and(CancelToken::is_cancelled)
+    }
+
+    pub(crate) fn report(&self, progress: Progress) {
+        if let Some(callback) = self.progress {
+            callback(progress);
+        }
+    }
+}
+
+impl fmt::Debug for Monitor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Monitor")
+            .field("progress", &self.progress.is_some())
+            .field("cancel", &self.cancel)
+            .finish()
+    }
+}
+
+/// A flag for cancelling jobs, which can be shared between threads.
+///
+/// ```
+/// use luhn::{CancelToken, Monitor};
+///
+/// let token = CancelToken::new();
+/// let monitor = Monitor::new().cancel_on(&token);
+/// token.cancel();
+/// let report = luhn::validate_lines_monitored("18\n".repeat(10).as_bytes(), &monitor).unwrap();
+/// assert!(report.cancelled);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// A token which hasn't been cancelled.
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    /// Cancels the jobs watching this token, or any clone of it.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Running totals for jobs split between threads.
+#[cfg(feature = "parallel")]
+#[derive(Debug, Default)]
+pub(crate) struct Totals {
+    bytes: AtomicU64,
+    matches: AtomicU64,
+}
+
+#[cfg(feature = "parallel")]
+impl Totals {
+    /// Adds a finished chunk's progress, reporting the new totals.
+    pub(crate) fn add(&self, chunk: Progress, monitor: &Monitor) {
+        let bytes = self.bytes.fetch_add(chunk.bytes, Ordering::Relaxed) + chunk.bytes;
+        let matches = self.matches.fetch_add(chunk.matches, Ordering::Relaxed) + chunk.matches;
+        monitor.report(Progress { bytes, matches });
+    }
+}
@@ -0,0 +1,68 @@
+//! UK Meter Point Administration Numbers (MPANs), which identify
+//! electricity supplies.
+//!
+//! The 13-digit MPAN core is a two-digit distributor ID, a ten-digit
+//! meter point ID and a check digit: the first twelve digits weighted by
+//! successive primes from 3 to 43, summed mod 11, then mod 10.  A full
+//! MPAN puts eight digits of supplementary data before the core.
+//!
+//! ```
+//! use luhn::mpan;
+//!
+//! assert!(mpan::valid("1200023305967"));
+//! assert!(mpan::valid("01 801 100 12 0002 3305 967"));
+//! assert_eq!(mpan::checksum(b"120002330596"), b'7');
+//! ```
+
+use crate::small::SmallString;
+
+const CORE_LEN: usize = 13;
+const FULL_LEN: usize = 21;
+const PRIMES: [u32; CORE_LEN - 1] = [3, 5, 7, 13, 17, 19, 23, 29, 31, 37, 41, 43];
+
+/// Computes the check digit for the first twelve digits of an MPAN core.
+///
+/// Input must be twelve ASCII digits; panics otherwise.  Returns an ASCII
+/// digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    assert_eq!(body.len(), CORE_LEN - 1, "Not an MPAN core: {:?}", body);
+    let sum: u32 = body
+        .iter()
+        .zip(PRIMES.iter())
+        .map(|(&b, &prime)| {
+            assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+            u32::from(b - b'0') * prime
+        })
+        .sum();
+    (sum % 11 % 10) as u8 + b'0'
+}
+
+/// Whether `s` is a 13-digit MPAN core or a 21-digit full MPAN, optionally
+/// grouped with spaces, whose check digit is good.
+pub fn valid(s: &str) -> bool {
+    let digits: SmallString = s.chars().filter(|&c| c != ' ').collect();
+    let bytes = digits.as_bytes();
+    if !(bytes.len() == CORE_LEN || bytes.len() == FULL_LEN)
+        || !bytes.iter().all(u8::is_ascii_digit)
+    {
+        return false;
+    }
+    let core = &bytes[bytes.len() - CORE_LEN..];
+    checksum(&core[..CORE_LEN - 1]) == core[CORE_LEN - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_mpans() {
+        assert!(valid("1200023305967"));
+        assert!(valid("1600012345677"));
+        assert!(!valid("1200023305968"));
+        assert!(!valid("120002330596"));
+        assert!(valid("018011001200023305967"));
+        assert!(!valid("18011001200023305967"));
+        assert!(!valid("120002330596x"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Spanish tax identification numbers: NIFs for citizens, and NIEs for
+//! foreigners.
+//!
+//! A NIF is eight digits and a control letter.  A NIE is `X`, `Y` or `Z`,
+//! seven digits and a control letter; the leading letter stands for 0, 1
+//! or 2 when computing the control letter.  The letter is the number
+//! mod 23, looked up in a table.
+//!
+//! ```
+//! use luhn::nif;
+//!
+//! assert!(nif::valid("12345678Z"));
+//! assert!(nif::valid("X1234567L"));
+//! assert_eq!(nif::control_letter(b"Y1234567"), b'X');
+//! ```
+
+const LETTERS: &[u8; 23] = b"TRWAGMYFPDXBNJZSQVHLCKE";
+const BODY_LEN: usize = 8;
+
+/// Computes the control letter for the eight characters of a NIF or NIE
+/// before it.
+///
+/// Input must be eight ASCII digits, or `X`, `Y` or `Z` and seven ASCII
+/// digits; panics otherwise.  Returns an ASCII uppercase letter.
+pub fn control_letter(body: &[u8]) -> u8 {
+    try_control_letter(body).unwrap_or_else(|| panic!("Not a NIF or NIE: {:?}", body))
+}
+
+/// Whether `s` is a NIF or NIE with a good control letter.  Letters must
+/// be uppercase.
+pub fn valid(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == BODY_LEN + 1 && try_control_letter(&bytes[..BODY_LEN]) == Some(bytes[BODY_LEN])
+}
+
+fn try_control_letter(body: &[u8]) -> Option<u8> {
+    let (first, digits) = body.split_first().filter(|_| body.len() == BODY_LEN)?;
+    let first = match first {
+        b'0'..=b'9' => first - b'0',
+        b'X' => 0,
+        b'Y' => 1,
+        b'Z' => 2,
+        _ => return None,
+    };
+    let number = digits.iter().try_fold(u32::from(first), |n, &b| {
+        b.is_ascii_digit().then(|| n * 10 + u32::from(b - b'0'))
+    })?;
+    Some(LETTERS[(number % 23) as usize])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_nifs() {
+        assert_eq!(control_letter(b"12345678"), b'Z');
+        assert_eq!(control_letter(b"00000000"), b'T');
+        assert!(valid("12345678Z"));
+        assert!(!valid("12345678A"));
+        assert!(!valid("12345678z"));
+        assert!(!valid("1234567Z"));
+        assert!(!valid("123456789Z"));
+    }
+
+    #[test]
+    fn validates_nies() {
+        assert!(valid("X1234567L"));
+        assert!(valid("Y1234567X"));
+        assert!(!valid("Z1234567L"));
+        assert!(!valid("W1234567L"));
+        assert!(!valid("XX234567L"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn control_letter_needs_eight_characters() {
+        control_letter(b"1234567");
+    }
+}
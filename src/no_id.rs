@@ -0,0 +1,95 @@
+//! Norwegian national identity numbers (fødselsnummer), and the
+//! D-numbers issued to foreigners.
+//!
+//! An identity number is eleven digits: the birth date as `DDMMYY`, a
+//! three-digit individual number, and two weighted mod-11 check digits,
+//! the second covering the first.  A D-number adds 4 to the first digit
+//! of the day.
+//!
+//! ```
+//! use luhn::no_id;
+//!
+//! assert!(no_id::valid("01010750160"));
+//! assert!(no_id::valid("41010750154"));
+//! assert!(!no_id::valid("01010750161"));
+//! ```
+
+const LEN: usize = 11;
+const FIRST_WEIGHTS: [u32; 9] = [3, 7, 6, 1, 8, 9, 4, 5, 2];
+const SECOND_WEIGHTS: [u32; 10] = [5, 4, 3, 2, 7, 6, 5, 4, 3, 2];
+
+/// Whether `s` is an 11-digit identity number or D-number with good check
+/// digits and a plausible birth date.
+///
+/// The year's century isn't checked, so 29 February is always allowed.
+pub fn valid(s: &str) -> bool {
+    if s.len() != LEN || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let mut d = [0; LEN];
+    for (d, b) in d.iter_mut().zip(s.bytes()) {
+        *d = u32::from(b - b'0');
+    }
+    let check_digit = |weights: &[u32]| {
+        let sum: u32 = d.iter().zip(weights).map(|(d, w)| d * w).sum();
+        match 11 - sum % 11 {
+            11 => Some(0),
+            10 => None,
+            k => Some(k),
+        }
+    };
+    if check_digit(&FIRST_WEIGHTS) != Some(d[9]) || check_digit(&SECOND_WEIGHTS) != Some(d[10]) {
+        return false;
+    }
+
+    let mut day = d[0] * 10 + d[1];
+    if day > 40 {
+        // A D-number.
+        day -= 40;
+    }
+    let month = d[2] * 10 + d[3];
+    let days = match month {
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_identity_numbers() {
+        assert!(valid("01010750160"));
+        assert!(valid("15077312363"));
+        assert!(valid("29020012380"));
+        assert!(!valid("01010750170"));
+        assert!(!valid("0101075016"));
+        assert!(!valid("0101075016x"));
+    }
+
+    #[test]
+    fn validates_d_numbers() {
+        assert!(valid("41010750154"));
+        assert!(!valid("41010750155"));
+    }
+
+    #[test]
+    fn rejects_impossible_check_digits() {
+        // The first check digit would have to be 10.
+        for k in 0..10 {
+            assert!(!valid(&format!("010107512{}0", k)));
+        }
+    }
+
+    #[test]
+    fn rejects_impossible_dates() {
+        // Good check digits, but no such dates.
+        assert!(!valid("32010750112"));
+        assert!(!valid("01300750105"));
+        assert!(!valid("30020750134"));
+    }
+}
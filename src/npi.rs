@@ -0,0 +1,64 @@
+//! US National Provider Identifiers (NPI), which identify health care
+//! providers.
+//!
+//! An NPI is ten digits, starting with 1 or 2.  The last is a Luhn check
+//! digit computed as if the number had the prefix `80840`, which makes it
+//! a valid ISO 7812 health card number.
+//!
+//! ```
+//! use luhn::npi;
+//!
+//! assert!(npi::valid("1234567893"));
+//! assert_eq!(npi::checksum(b"123456789"), b'3');
+//! ```
+
+const LEN: usize = 10;
+const PREFIX: &[u8] = b"80840";
+
+/// Computes the check digit for the first nine digits of an NPI.
+///
+/// Input must be nine ASCII digits; panics otherwise.  Returns an ASCII
+/// digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    assert!(
+        body.len() == LEN - 1 && body.iter().all(u8::is_ascii_digit),
+        "Not an NPI body: {:?}",
+        body
+    );
+    let mut prefixed = [0; PREFIX.len() + LEN - 1];
+    prefixed[..PREFIX.len()].copy_from_slice(PREFIX);
+    prefixed[PREFIX.len()..].copy_from_slice(body);
+    crate::checksum(&prefixed)
+}
+
+/// Whether `s` is a 10-digit NPI, starting with 1 or 2, with a good check
+/// digit.
+pub fn valid(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == LEN
+        && bytes.iter().all(u8::is_ascii_digit)
+        && matches!(bytes[0], b'1' | b'2')
+        && checksum(&bytes[..LEN - 1]) == bytes[LEN - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_npis() {
+        assert!(valid("1234567893"));
+        assert!(!valid("1234567890"));
+        assert!(!valid("123456789"));
+        assert!(!valid("12345678931"));
+        assert!(!valid("123456789a"));
+        // Luhn-valid with the prefix, but NPIs start with 1 or 2.
+        assert!(!valid("3234567899"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn checksum_needs_nine_digits() {
+        checksum(b"12345678");
+    }
+}
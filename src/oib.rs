@@ -0,0 +1,32 @@
+//! Croatian personal identification numbers (OIB).
+//!
+//! An OIB is ten digits and an [ISO/IEC 7064 MOD 11,10](crate::iso7064::mod_11_10)
+//! check digit, and identifies both people and companies.
+//!
+//! ```
+//! assert!(luhn::oib::valid("69435151530"));
+//! assert!(!luhn::oib::valid("69435151531"));
+//! ```
+
+use crate::iso7064::mod_11_10;
+
+const LEN: usize = 11;
+
+/// Whether `s` is an 11-digit OIB with a good check digit.
+pub fn valid(s: &str) -> bool {
+    s.len() == LEN && mod_11_10::valid(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_oibs() {
+        assert!(valid("69435151530"));
+        assert!(valid("94577403194"));
+        assert!(!valid("9457740319"));
+        assert!(!valid("945774031941"));
+        assert!(!valid("9457740319a"));
+    }
+}
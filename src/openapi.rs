@@ -0,0 +1,101 @@
+//! utoipa `ToSchema` implementations for the newtypes.
+
+use std::borrow::Cow;
+
+use utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+use crate::{Imei, Isin, Pan};
+
+fn string_schema(
+    format: &str,
+    pattern: &str,
+    len: (usize, usize),
+    description: &str,
+    example: &str,
+) -> RefOr<Schema> {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .format(Some(SchemaFormat::Custom(format.to_string())))
+        .pattern(Some(pattern))
+        .min_length(Some(len.0))
+        .max_length(Some(len.1))
+        .description(Some(description))
+        .examples([example])
+        .into()
+}
+
+impl PartialSchema for Pan {
+    fn schema() -> RefOr<Schema> {
+        string_schema(
+            "pan",
+            "^[0-9]{12,19}$",
+            (12, 19),
+            "A payment card number.  The last digit is a Luhn check digit.",
+            "4111111111111111",
+        )
+    }
+}
+
+impl ToSchema for Pan {
+    fn name() -> Cow<'static, str> {
+        "Pan".into()
+    }
+}
+
+impl PartialSchema for Isin {
+    fn schema() -> RefOr<Schema> {
+        string_schema(
+            "isin",
+            "^[A-Z]{2}[0-9A-Z]{9}[0-9]$",
+            (12, 12),
+            "An International Securities Identification Number.  The last digit is a Luhn \
+             check digit, computed after converting letters to numbers.",
+            "US0378331005",
+        )
+    }
+}
+
+impl ToSchema for Isin {
+    fn name() -> Cow<'static, str> {
+        "Isin".into()
+    }
+}
+
+impl PartialSchema for Imei {
+    fn schema() -> RefOr<Schema> {
+        string_schema(
+            "imei",
+            "^[0-9]{15}$",
+            (15, 15),
+            "An International Mobile Equipment Identity.  The last digit is a Luhn check digit.",
+            "490154203237518",
+        )
+    }
+}
+
+impl ToSchema for Imei {
+    fn name() -> Cow<'static, str> {
+        "Imei".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_are_valid() {
+        for (schema, parse) in [
+            (Pan::schema(), (|s| Pan::new(s).is_ok()) as fn(&str) -> bool),
+            (Isin::schema(), |s| Isin::new(s).is_ok()),
+            (Imei::schema(), |s| Imei::new(s).is_ok()),
+        ] {
+            let json = serde_json::to_value(&schema).unwrap();
+            let example = json["examples"][0].as_str().unwrap();
+            assert!(parse(example), "{} is a bad example", example);
+            assert_eq!(json["type"], "string");
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Payment card numbers.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::integer::decimal_len;
+use crate::{strip_grouping, validate_with, LuhnError, ValidateConfig};
+
+/// A payment card network, as identified by the leading digits of a PAN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum CardNetwork {
+    Visa,
+    Mastercard,
+    AmericanExpress,
+    Discover,
+    DinersClub,
+    Jcb,
+    UnionPay,
+}
+
+impl CardNetwork {
+    /// Guesses the network from the leading digits of a card number.
+    ///
+    /// Only the prefix is examined; the number isn't validated.
+    pub fn detect(number: &str) -> Option<CardNetwork> {
+        let prefix = |n: usize| {
+            number
+                .get(..n)
+                .filter(|p| p.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|p| p.parse::<u32>().ok())
+        };
+        match (prefix(1), prefix(2), prefix(3), prefix(4)) {
+            (_, Some(34), _, _) | (_, Some(37), _, _) => Some(CardNetwork::AmericanExpress),
+            (Some(4), _, _, _) => Some(CardNetwork::Visa),
+            (_, Some(51..=55), _, _) | (_, _, _, Some(2221..=2720)) => {
+                Some(CardNetwork::Mastercard)
+            }
+            (_, _, _, Some(6011)) | (_, _, Some(644..=649), _) | (_, Some(65), _, _) => {
+                Some(CardNetwork::Discover)
+            }
+            (_, _, _, Some(3528..=3589)) => Some(CardNetwork::Jcb),
+            (_, Some(36), _, _) | (_, Some(38..=39), _, _) | (_, _, Some(300..=305), _) => {
+                Some(CardNetwork::DinersClub)
+            }
+            (_, Some(62), _, _) => Some(CardNetwork::UnionPay),
+            _ => None,
+        }
+    }
+}
+
+/// A validated primary account number (ie. a payment card number).
+///
+/// A `Pan` is between 12 and 19 ASCII digits long and passes the Luhn
+/// check.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
+pub struct Pan(pub(crate) String);
+
+impl Pan {
+    /// Validates `number` as a PAN.
+    pub fn new(number: &str) -> Result<Pan, LuhnError> {
+        validate_with(number, &ValidateConfig::PAN)?;
+        Ok(Pan(number.to_string()))
+    }
+
+    /// Validates `n` written as `len` digits, padded with leading zeros,
+    /// as a PAN.  The inverse of [`to_u64`](Pan::to_u64), given the
+    /// original length.
+    ///
+    /// Fails with [`LuhnError::TooLong`] if `n` needs more than `len`
+    /// digits, rather than silently producing a longer number.
+    ///
+    /// ```
+    /// use luhn::{LuhnError, Pan};
+    ///
+    /// let pan = Pan::new("4111111111111111").unwrap();
+    /// assert_eq!(Pan::from_u64_with_len(pan.to_u64(), 16), Ok(pan));
+    /// assert_eq!(
+    ///     Pan::from_u64_with_len(4111111111111111, 12),
+    ///     Err(LuhnError::TooLong { len: 16, max: 12 })
+    /// );
+    /// ```
+    pub fn from_u64_with_len(n: u64, len: usize) -> Result<Pan, LuhnError> {
+        let digits = decimal_len(n);
+        if digits > len {
+            return Err(LuhnError::TooLong {
+                len: digits,
+                max: len,
+            });
+        }
+        Pan::new(&format!("{:0len$}", n, len = len))
+    }
+
+    /// The card number as an integer.
+    ///
+    /// Every PAN fits, being at most 19 digits.  Leading zeros are lost, so
+    /// keep [`len`](Pan::len) alongside the integer to get the same `Pan`
+    /// back with [`from_u64_with_len`](Pan::from_u64_with_len).
+    pub fn to_u64(&self) -> u64 {
+        self.0.parse().unwrap()
+    }
+
+    /// The number of digits in the card number.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The card number as a string of digits.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The network which issued this card, if we recognise it.
+    pub fn network(&self) -> Option<CardNetwork> {
+        CardNetwork::detect(&self.0)
+    }
+
+    /// The first six digits, which identify the issuer.
+    pub fn bin(&self) -> &str {
+        &self.0[..6]
+    }
+
+    /// The last four digits, which are commonly shown to cardholders.
+    pub fn last_four(&self) -> &str {
+        &self.0[self.0.len() - 4..]
+    }
+
+    /// The card number with all but the last four digits replaced by
+    /// `*`, eg. `"************1111"`.
+    pub fn masked(&self) -> String {
+        let mut masked = "*".repeat(self.0.len() - 4);
+        masked.push_str(self.last_four());
+        masked
+    }
+
+    /// The card number split into groups as printed on the card, eg.
+    /// `"4111 1111 1111 1111"`, or `"3782 822463 10005"` for American
+    /// Express.  Also available as `format!("{:#}", pan)`.
+    pub fn formatted(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Splits the number into the groups it's printed in.
+    fn groups(&self) -> impl Iterator<Item = &str> {
+        let s = self.0.as_str();
+        let leading: &[usize] = match (self.network(), s.len()) {
+            (Some(CardNetwork::AmericanExpress), 15) => &[4, 6, 5],
+            (Some(CardNetwork::DinersClub), 14) => &[4, 6, 4],
+            _ => &[],
+        };
+        let mut rest = s;
+        let mut lens = leading.iter();
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let len = lens.next().copied().unwrap_or(4).min(rest.len());
+            let (group, tail) = rest.split_at(len);
+            rest = tail;
+            Some(group)
+        })
+    }
+}
+
+/// Parses like [`Pan::new`], but ignores the spaces or hyphens a card
+/// number is usually grouped with, as in `"4111 1111 1111 1111"`, and
+/// any surrounding whitespace.  Error positions refer to the bare digits.
+impl FromStr for Pan {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Pan, LuhnError> {
+        Pan::new(&strip_grouping(s))
+    }
+}
+
+/// Writes the bare digits, or with the alternate flag (`{:#}`) the digits
+/// in groups, as [`Pan::formatted`] does.
+impl fmt::Display for Pan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return f.write_str(&self.0);
+        }
+        for (i, group) in self.groups().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(group)?;
+        }
+        Ok(())
+    }
+}
+
+impl AsRef<str> for Pan {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Pan {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_good_pans() {
+        let pan: Pan = "4111111111111111".parse().unwrap();
+        assert_eq!(pan.as_str(), "4111111111111111");
+        assert_eq!(pan.bin(), "411111");
+        assert_eq!(pan.last_four(), "1111");
+        assert_eq!(pan.masked(), "************1111");
+        assert_eq!(pan.network(), Some(CardNetwork::Visa));
+    }
+
+    #[test]
+    fn rejects_bad_pans() {
+        assert_eq!(
+            Pan::new("41111111111"),
+            Err(LuhnError::TooShort { len: 11, min: 12 })
+        );
+        assert_eq!(
+            Pan::new("41111111111111111111"),
+            Err(LuhnError::TooLong { len: 20, max: 19 })
+        );
+        assert_eq!(
+            Pan::new("4111-11111111111"),
+            Err(LuhnError::InvalidCharacter {
+                position: 4,
+                character: '-'
+            })
+        );
+        assert_eq!(
+            Pan::new("4111111111111112"),
+            Err(LuhnError::BadCheckDigit {
+                position: 15,
+                expected: '1',
+                found: '2'
+            })
+        );
+    }
+
+    #[test]
+    fn parses_grouped_pans() {
+        let pan = Pan::new("4111111111111111").unwrap();
+        assert_eq!("4111 1111 1111 1111".parse(), Ok(pan.clone()));
+        assert_eq!("4111-1111-1111-1111\n".parse(), Ok(pan.clone()));
+        assert_eq!(pan.formatted().parse(), Ok(pan));
+        assert_eq!(
+            "4111 1111 1111 1112".parse::<Pan>(),
+            Err(LuhnError::BadCheckDigit {
+                position: 15,
+                expected: '1',
+                found: '2'
+            })
+        );
+    }
+
+    #[test]
+    fn formats_in_groups() {
+        let formatted = |s: &str| Pan::new(s).unwrap().formatted();
+        assert_eq!(formatted("4111111111111111"), "4111 1111 1111 1111");
+        assert_eq!(formatted("378282246310005"), "3782 822463 10005");
+        assert_eq!(formatted("30569309025904"), "3056 930902 5904");
+        assert_eq!(formatted("4222222222222"), "4222 2222 2222 2");
+        assert_eq!(formatted("6200000000000000000"), "6200 0000 0000 0000 000");
+        let pan = Pan::new("4111111111111111").unwrap();
+        assert_eq!(pan.to_string(), "4111111111111111");
+        assert_eq!(format!("{:#}", pan), "4111 1111 1111 1111");
+    }
+
+    #[test]
+    fn round_trips_through_u64() {
+        for s in ["4111111111111111", "0000000000000000", "0004111111111119"] {
+            let pan = Pan::new(s).unwrap();
+            assert_eq!(Pan::from_u64_with_len(pan.to_u64(), pan.len()), Ok(pan));
+        }
+        assert_eq!(
+            Pan::new("0004111111111119").unwrap().to_u64(),
+            4111111111119
+        );
+        assert_eq!(
+            Pan::from_u64_with_len(u64::MAX, 19),
+            Err(LuhnError::TooLong { len: 20, max: 19 })
+        );
+        assert_eq!(
+            Pan::from_u64_with_len(4111111111111111, 20),
+            Err(LuhnError::TooLong { len: 20, max: 19 })
+        );
+    }
+
+    #[test]
+    fn looks_up_by_str() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let pans = ["5555555555554444", "4111111111111111"].map(|s| Pan::new(s).unwrap());
+        let hashed: HashSet<Pan> = pans.iter().cloned().collect();
+        assert!(hashed.contains("4111111111111111"));
+        let sorted: BTreeSet<Pan> = pans.iter().cloned().collect();
+        assert!(sorted.contains("5555555555554444"));
+        assert_eq!(sorted.first().unwrap().as_str(), "4111111111111111");
+    }
+
+    #[test]
+    fn detects_networks() {
+        use CardNetwork::*;
+        assert_eq!(CardNetwork::detect("4242424242424242"), Some(Visa));
+        assert_eq!(CardNetwork::detect("5555555555554444"), Some(Mastercard));
+        assert_eq!(CardNetwork::detect("2223003122003222"), Some(Mastercard));
+        assert_eq!(
+            CardNetwork::detect("378282246310005"),
+            Some(AmericanExpress)
+        );
+        assert_eq!(CardNetwork::detect("6011111111111117"), Some(Discover));
+        assert_eq!(CardNetwork::detect("30569309025904"), Some(DinersClub));
+        assert_eq!(CardNetwork::detect("3530111333300000"), Some(Jcb));
+        assert_eq!(CardNetwork::detect("6200000000000005"), Some(UnionPay));
+        assert_eq!(CardNetwork::detect("9999999999999995"), None);
+        assert_eq!(CardNetwork::detect(""), None);
+    }
+}
@@ -0,0 +1,342 @@
+//! Matching identifiers against a display format and a check digit at
+//! once.
+//!
+//! A [`Pattern`] is written with `#` for any digit, `?` for any digit or
+//! uppercase letter, `C` for the Luhn check digit, and anything else
+//! standing for itself; a backslash makes the next character stand for
+//! itself too, as in `\C`.
+//!
+//! The check digit covers all the digits and uppercase letters, whether
+//! they're written in the pattern or not, with letters counting as two
+//! digits as in [`checksum`](crate::checksum).  Anything else, such as
+//! spaces, hyphens and lowercase letters, separates the fields.  The same
+//! patterns lay out [`generate_from_template`](crate::generate_from_template)'s
+//! output.
+//!
+//! ```
+//! use luhn::pattern::Pattern;
+//!
+//! let pattern = Pattern::new("####-####-####-###C").unwrap();
+//! assert_eq!(
+//!     pattern.extract("4111-1111-1111-1111"),
+//!     Ok(vec!["4111", "1111", "1111", "1111"])
+//! );
+//! assert!(!pattern.matches("4111 1111 1111 1111"));
+//! assert!(!pattern.matches("4111-1111-1111-1112"));
+//!
+//! let isin = Pattern::new("US?????????C").unwrap();
+//! assert!(isin.matches("US0378331005"));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{LuhnError, Validator};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Slot {
+    /// `#`.
+    Digit,
+    /// `?`.
+    Alphanumeric,
+    /// `C`.
+    Check,
+    Literal(char),
+}
+
+/// Whether `c` counts towards the check digit.
+fn counts(c: char) -> bool {
+    c.is_ascii_digit() || c.is_ascii_uppercase()
+}
+
+/// A compiled pattern; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    source: String,
+    slots: Vec<Slot>,
+}
+
+impl Pattern {
+    /// Compiles `pattern`, which needs exactly one `C`.
+    pub fn new(pattern: &str) -> Result<Pattern, PatternError> {
+        let mut slots = Vec::with_capacity(pattern.len());
+        let mut check = false;
+        let mut chars = pattern.char_indices();
+        while let Some((position, c)) = chars.next() {
+            slots.push(match c {
+                '#' => Slot::Digit,
+                '?' => Slot::Alphanumeric,
+                'C' if check => return Err(PatternError::SecondCheckDigit { position }),
+                'C' => {
+                    check = true;
+                    Slot::Check
+                }
+                '\\' => match chars.next() {
+                    Some((_, c)) => Slot::Literal(c),
+                    None => return Err(PatternError::TrailingBackslash),
+                },
+                c => Slot::Literal(c),
+            });
+        }
+        if !check {
+            return Err(PatternError::NoCheckDigit);
+        }
+        Ok(Pattern {
+            source: pattern.to_string(),
+            slots,
+        })
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// One slot for each character a match has.
+    #[cfg(feature = "rand")]
+    pub(crate) fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    /// Whether `s` fits the pattern and has a good check digit.
+    pub fn matches(&self, s: &str) -> bool {
+        self.check(s, |_| {}).is_ok()
+    }
+
+    /// Validates `s` against the pattern, returning its fields: the runs
+    /// of digits and uppercase letters between separators.
+    ///
+    /// Length errors count characters; positions are byte offsets into
+    /// `s`, as usual.
+    pub fn extract<'a>(&self, s: &'a str) -> Result<Vec<&'a str>, LuhnError> {
+        let mut fields = Vec::new();
+        self.check(s, |field| fields.push(field))?;
+        Ok(fields)
+    }
+
+    /// Validates `s`, passing its fields to `field` as they're found.
+    fn check<'a>(&self, s: &'a str, mut field: impl FnMut(&'a str)) -> Result<(), LuhnError> {
+        let len = s.chars().count();
+        if len < self.slots.len() {
+            return Err(LuhnError::TooShort {
+                len,
+                min: self.slots.len(),
+            });
+        }
+        if len > self.slots.len() {
+            return Err(LuhnError::TooLong {
+                len,
+                max: self.slots.len(),
+            });
+        }
+
+        let mut check = (0, '0');
+        let mut field_start = None;
+        for ((position, character), &slot) in s.char_indices().zip(&self.slots) {
+            let fits = match slot {
+                Slot::Digit | Slot::Check => character.is_ascii_digit(),
+                Slot::Alphanumeric => counts(character),
+                Slot::Literal(c) => character == c,
+            };
+            if !fits {
+                return Err(LuhnError::InvalidCharacter {
+                    position,
+                    character,
+                });
+            }
+            if slot == Slot::Check {
+                check = (position, character);
+            }
+            match (counts(character), field_start) {
+                (true, None) => field_start = Some(position),
+                (false, Some(start)) => {
+                    field(&s[start..position]);
+                    field_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = field_start {
+            field(&s[start..]);
+        }
+
+        let (position, found) = check;
+        if self.sum(s, found) % 10 != 0 {
+            // Each value of the check digit gives a different sum mod 10,
+            // wherever it is, so exactly one of them validates.
+            let expected = ('0'..='9').find(|&d| self.sum(s, d) % 10 == 0).unwrap();
+            return Err(LuhnError::BadCheckDigit {
+                position,
+                expected,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// The Luhn sum of `s`, which fits the pattern, with `check` as its
+    /// check digit.  Worked out from the right, where the doubling starts.
+    fn sum(&self, s: &str, check: char) -> u32 {
+        let mut sum = 0;
+        let mut double = false;
+        for (c, &slot) in s.chars().rev().zip(self.slots.iter().rev()) {
+            let c = if slot == Slot::Check { check } else { c };
+            if !counts(c) {
+                continue;
+            }
+            // A letter's two digits, taken from the right.
+            let value = c.to_digit(36).unwrap();
+            let digits = [value % 10, value / 10];
+            for &d in &digits[..if value < 10 { 1 } else { 2 }] {
+                sum += if double { d / 5 + (2 * d) % 10 } else { d };
+                double = !double;
+            }
+        }
+        sum
+    }
+}
+
+impl Validator for Pattern {
+    fn name(&self) -> &str {
+        &self.source
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.matches(input)
+    }
+}
+
+/// Why a [`Pattern`] couldn't be compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatternError {
+    /// There's no `C`.
+    NoCheckDigit,
+    /// There's another `C` at byte offset `position`.
+    SecondCheckDigit { position: usize },
+    /// The pattern ends in a backslash, with nothing to escape.
+    TrailingBackslash,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::NoCheckDigit => f.write_str("pattern has no check digit 'C'"),
+            PatternError::SecondCheckDigit { position } => {
+                write!(
+                    f,
+                    "pattern has a second check digit at position {}",
+                    position
+                )
+            }
+            PatternError::TrailingBackslash => f.write_str("pattern ends in a backslash"),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_fields() {
+        let pattern = Pattern::new("4###-####-####-###C").unwrap();
+        assert_eq!(
+            pattern.extract("4111-1111-1111-1111"),
+            Ok(vec!["4111", "1111", "1111", "1111"])
+        );
+        let pattern = Pattern::new("id-##########C").unwrap();
+        assert_eq!(pattern.extract("id-79927398713"), Ok(vec!["79927398713"]));
+        let pattern = Pattern::new("(###) C").unwrap();
+        assert_eq!(pattern.extract("(799) 7"), Ok(vec!["799", "7"]));
+    }
+
+    #[test]
+    fn rejects_mismatches() {
+        let pattern = Pattern::new("4###-####-####-###C").unwrap();
+        assert_eq!(
+            pattern.extract("5111-1111-1111-1111"),
+            Err(LuhnError::InvalidCharacter {
+                position: 0,
+                character: '5'
+            })
+        );
+        assert_eq!(
+            pattern.extract("4111-111a-1111-1111"),
+            Err(LuhnError::InvalidCharacter {
+                position: 8,
+                character: 'a'
+            })
+        );
+        assert_eq!(
+            pattern.extract("4111-1111-1111-111"),
+            Err(LuhnError::TooShort { len: 18, min: 19 })
+        );
+        assert_eq!(
+            pattern.extract("4111-1111-1111-1112"),
+            Err(LuhnError::BadCheckDigit {
+                position: 18,
+                expected: '1',
+                found: '2'
+            })
+        );
+    }
+
+    #[test]
+    fn check_digit_can_go_anywhere() {
+        let pattern = Pattern::new("C-##########").unwrap();
+        assert!(pattern.matches("4-7992739871"));
+        assert_eq!(
+            pattern.extract("3-7992739871"),
+            Err(LuhnError::BadCheckDigit {
+                position: 0,
+                expected: '4',
+                found: '3'
+            })
+        );
+    }
+
+    #[test]
+    fn letters_count_towards_the_check_digit() {
+        let isin = Pattern::new("US?????????C").unwrap();
+        assert_eq!(isin.extract("US0378331005"), Ok(vec!["US0378331005"]));
+        assert!(!isin.matches("US0378331006"));
+        assert!(Pattern::new("???????????C")
+            .unwrap()
+            .matches("AU0000XVGZA3"));
+        // Written in the pattern or not, uppercase letters count.
+        let pattern = Pattern::new("ID-##########C").unwrap();
+        assert_eq!(
+            pattern.extract("ID-79927398713"),
+            Err(LuhnError::BadCheckDigit {
+                position: 13,
+                expected: '8',
+                found: '3'
+            })
+        );
+        assert_eq!(
+            pattern.extract("ID-79927398718"),
+            Ok(vec!["ID", "79927398718"])
+        );
+    }
+
+    #[test]
+    fn escapes_stand_for_themselves() {
+        let pattern = Pattern::new("\\#\\C-##C").unwrap();
+        assert_eq!(pattern.as_str(), "\\#\\C-##C");
+        assert_eq!(pattern.extract("#C-120"), Ok(vec!["C", "120"]));
+        assert!(!pattern.matches("#C-121"));
+        assert_eq!(Pattern::new("###C\\"), Err(PatternError::TrailingBackslash));
+    }
+
+    #[test]
+    fn needs_one_check_digit() {
+        assert_eq!(Pattern::new("####"), Err(PatternError::NoCheckDigit));
+        assert_eq!(
+            Pattern::new("##C-C"),
+            Err(PatternError::SecondCheckDigit { position: 4 })
+        );
+    }
+}
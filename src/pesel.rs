@@ -0,0 +1,115 @@
+//! Polish national identification numbers (PESEL).
+//!
+//! A PESEL is eleven digits: the birth date as `YYMMDD`, with the century
+//! folded into the month, a serial number whose last digit gives the
+//! holder's sex, and a weighted mod-10 check digit.
+//!
+//! ```
+//! use luhn::pesel::{self, Sex};
+//!
+//! assert!(pesel::valid("44051401458"));
+//! let info = pesel::parse("44051401458").unwrap();
+//! assert_eq!((info.year, info.month, info.day), (1944, 5, 14));
+//! assert_eq!(info.sex, Sex::Male);
+//! ```
+
+const LEN: usize = 11;
+const WEIGHTS: [u32; LEN - 1] = [1, 3, 7, 9, 1, 3, 7, 9, 1, 3];
+
+/// The holder's sex, as recorded in a PESEL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sex {
+    Female,
+    Male,
+}
+
+/// What a valid PESEL says about its holder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pesel {
+    pub year: u16,
+    /// From 1 to 12.
+    pub month: u8,
+    pub day: u8,
+    pub sex: Sex,
+}
+
+/// Whether `s` is an 11-digit PESEL with a good check digit and a birth
+/// date which exists.
+pub fn valid(s: &str) -> bool {
+    parse(s).is_some()
+}
+
+/// Validates `s` as [`valid`] does, returning the birth date and sex it
+/// encodes.
+pub fn parse(s: &str) -> Option<Pesel> {
+    if s.len() != LEN || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut digits = [0; LEN];
+    for (d, b) in digits.iter_mut().zip(s.bytes()) {
+        *d = u32::from(b - b'0');
+    }
+    let sum: u32 = digits.iter().zip(WEIGHTS.iter()).map(|(d, w)| d * w).sum();
+    if (10 - sum % 10) % 10 != digits[LEN - 1] {
+        return None;
+    }
+
+    let number = |i: usize| (digits[i] * 10 + digits[i + 1]) as u16;
+    let (century, month) = match number(2) {
+        m @ 1..=12 => (1900, m),
+        m @ 21..=32 => (2000, m - 20),
+        m @ 41..=52 => (2100, m - 40),
+        m @ 61..=72 => (2200, m - 60),
+        m @ 81..=92 => (1800, m - 80),
+        _ => return None,
+    };
+    let year = century + number(0);
+    let day = number(4);
+    if day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(Pesel {
+        year,
+        month: month as u8,
+        day: day as u8,
+        sex: if digits[9] % 2 == 0 {
+            Sex::Female
+        } else {
+            Sex::Male
+        },
+    })
+}
+
+fn days_in_month(year: u16, month: u16) -> u16 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_birth_dates() {
+        let parse = |s| parse(s).map(|p| (p.year, p.month, p.day, p.sex));
+        assert_eq!(parse("44051401458"), Some((1944, 5, 14, Sex::Male)));
+        assert_eq!(parse("02270803624"), Some((2002, 7, 8, Sex::Female)));
+        assert_eq!(parse("00222900009"), Some((2000, 2, 29, Sex::Female)));
+        assert_eq!(parse("04222900007"), Some((2004, 2, 29, Sex::Female)));
+    }
+
+    #[test]
+    fn rejects_bad_pesels() {
+        assert!(!valid("44051401459"));
+        assert!(!valid("4405140145"));
+        assert!(!valid("4405140145a"));
+        // Good check digits, but no such dates.
+        assert!(!valid("44133101456"));
+        assert!(!valid("44023001455"));
+        assert!(!valid("00822900007"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Python bindings, via PyO3.
+//!
+//! Build and install the extension module with
+//! [maturin](https://www.maturin.rs/): `maturin develop --release`.
+//! The settings in `pyproject.toml` enable this module.
+//!
+//! ```python
+//! import luhn
+//!
+//! luhn.valid("4111111111111111")  # True
+//! luhn.valid("4111 1111 1111 1111")  # False
+//! luhn.checksum("7992739871")  # "3"
+//! luhn.generate(16, prefix="411111")
+//! luhn.find_candidates("card 4111 1111 1111 1111")  # [(5, 24, "4111111111111111")]
+//! ```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Validates the given string of ASCII digits using the Luhn algorithm.
+/// Empty input, or anything other than digits, is invalid, as with
+/// `luhn_polars.luhn_valid`.
+#[pyfunction]
+fn valid(s: &str) -> bool {
+    crate::check_digits(s).is_ok()
+}
+
+/// Computes the check digit for the given uppercase alphanumeric string.
+#[pyfunction]
+fn checksum(s: &str) -> PyResult<String> {
+    match crate::try_checksum(s.as_bytes()) {
+        Ok(check) => Ok((check as char).to_string()),
+        Err(e) => Err(PyValueError::new_err(e.to_string())),
+    }
+}
+
+/// Generates a random valid number of the given length.
+///
+/// Pass `seed` for reproducible output.
+#[pyfunction]
+#[pyo3(signature = (length, prefix = "", seed = None))]
+fn generate(length: usize, prefix: &str, seed: Option<u64>) -> PyResult<String> {
+    use rand::SeedableRng;
+
+    if !prefix.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(PyValueError::new_err("prefix must be decimal digits"));
+    }
+    if prefix.len() >= length {
+        return Err(PyValueError::new_err(
+            "length must leave room for a check digit after the prefix",
+        ));
+    }
+    Ok(match seed {
+        Some(seed) => crate::generate_with_prefix(
+            prefix,
+            length,
+            &mut rand_chacha::ChaCha8Rng::seed_from_u64(seed),
+        ),
+        None => crate::generate_with_prefix(prefix, length, &mut rand::thread_rng()),
+    })
+}
+
+/// Finds card numbers in the given text, with the default scanner
+/// settings.
+///
+/// Returns `(start, end, digits)` tuples, where `text[start:end]` is the
+/// number as it appears and `digits` has its separators removed.
+#[pyfunction]
+fn find_candidates(text: &str) -> Vec<(usize, usize, String)> {
+    let mut byte_pos = 0;
+    let mut char_pos = 0;
+    let mut to_chars = |byte: usize| {
+        char_pos += text[byte_pos..byte].chars().count();
+        byte_pos = byte;
+        char_pos
+    };
+    crate::scan::find_candidates(text, &Default::default())
+        .into_iter()
+        .map(|m| (to_chars(m.range.start), to_chars(m.range.end), m.digits))
+        .collect()
+}
+
+#[pymodule]
+fn luhn(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(valid, m)?)?;
+    m.add_function(wrap_pyfunction!(checksum, m)?)?;
+    m.add_function(wrap_pyfunction!(generate, m)?)?;
+    m.add_function(wrap_pyfunction!(find_candidates, m)?)?;
+    Ok(())
+}
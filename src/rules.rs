@@ -0,0 +1,242 @@
+//! Building validators out of simple rules.
+//!
+//! Most identifiers are a few rules stacked together: a length, some
+//! allowed prefixes and a check digit.  The functions here make the
+//! rules, and [`Validator::and`] and [`Validator::or`] stack them:
+//!
+//! ```
+//! use luhn::rules::{length, luhn, prefix_in, prefix_range};
+//! use luhn::Validator;
+//!
+//! let visa_or_mastercard = length(12..=19)
+//!     .and(prefix_in(&["4"]).or(prefix_range(51..=55)))
+//!     .and(luhn())
+//!     .named("visa-or-mastercard");
+//! assert!(visa_or_mastercard.validate("4111111111111111"));
+//! assert!(visa_or_mastercard.validate("5500000000000004"));
+//! assert!(!visa_or_mastercard.validate("378282246310005"));
+//! ```
+
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+use crate::integer::decimal_len;
+use crate::{Algorithm, Validator};
+
+/// Accepts input whose length in bytes is within `range`.
+pub fn length(range: impl RangeBounds<usize>) -> Length {
+    Length {
+        start: range.start_bound().cloned(),
+        end: range.end_bound().cloned(),
+    }
+}
+
+/// Accepts input starting with any of `prefixes`.
+pub fn prefix_in(prefixes: &[&str]) -> Prefixes {
+    Prefixes(prefixes.iter().map(|p| p.to_string()).collect())
+}
+
+/// Accepts input whose leading digits, read as an integer, are within
+/// `range`, eg. `51..=55` for the `"51"` to `"55"` Mastercard prefixes.
+///
+/// Both ends of `range` must have the same number of digits, which is how
+/// many are read; panics otherwise.
+pub fn prefix_range(range: RangeInclusive<u32>) -> PrefixRange {
+    let width = decimal_len(u64::from(*range.start()));
+    assert_eq!(
+        width,
+        decimal_len(u64::from(*range.end())),
+        "Prefix range ends differ in length: {:?}",
+        range
+    );
+    PrefixRange { range, width }
+}
+
+/// Accepts decimal digits ending in a good Luhn check digit.
+pub fn luhn() -> Algorithm {
+    Algorithm::Luhn
+}
+
+/// See [`length`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Length {
+    start: Bound<usize>,
+    end: Bound<usize>,
+}
+
+impl Validator for Length {
+    fn name(&self) -> &str {
+        "length"
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        (self.start, self.end).contains(&input.len())
+    }
+}
+
+/// See [`prefix_in`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prefixes(Vec<String>);
+
+impl Validator for Prefixes {
+    fn name(&self) -> &str {
+        "prefix"
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.0
+            .iter()
+            .any(|prefix| input.starts_with(prefix.as_str()))
+    }
+}
+
+/// See [`prefix_range`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixRange {
+    range: RangeInclusive<u32>,
+    width: usize,
+}
+
+impl Validator for PrefixRange {
+    fn name(&self) -> &str {
+        "prefix"
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        input
+            .get(..self.width)
+            .filter(|p| p.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|p| p.parse().ok())
+            .is_some_and(|p| self.range.contains(&p))
+    }
+}
+
+/// Accepts input which both validators accept; see [`Validator::and`].
+#[derive(Debug, Clone)]
+pub struct And<A, B> {
+    first: A,
+    second: B,
+    name: String,
+}
+
+impl<A: Validator, B: Validator> And<A, B> {
+    pub(crate) fn new(first: A, second: B) -> And<A, B> {
+        let name = format!("{} and {}", first.name(), second.name());
+        And {
+            first,
+            second,
+            name,
+        }
+    }
+}
+
+impl<A: Validator, B: Validator> Validator for And<A, B> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.first.validate(input) && self.second.validate(input)
+    }
+}
+
+/// Accepts input which either validator accepts; see [`Validator::or`].
+#[derive(Debug, Clone)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+    name: String,
+}
+
+impl<A: Validator, B: Validator> Or<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Or<A, B> {
+        let name = format!("{} or {}", first.name(), second.name());
+        Or {
+            first,
+            second,
+            name,
+        }
+    }
+}
+
+impl<A: Validator, B: Validator> Validator for Or<A, B> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.first.validate(input) || self.second.validate(input)
+    }
+}
+
+/// A validator under another name; see [`Validator::named`].
+#[derive(Debug, Clone)]
+pub struct Named<V> {
+    inner: V,
+    name: String,
+}
+
+impl<V> Named<V> {
+    pub(crate) fn new(inner: V, name: String) -> Named<V> {
+        Named { inner, name }
+    }
+}
+
+impl<V: Validator> Validator for Named<V> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.inner.validate(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_lengths() {
+        assert!(length(2..4).validate("123"));
+        assert!(!length(2..4).validate("1234"));
+        assert!(length(..=4).validate(""));
+        assert!(length(4..).validate("1234567890"));
+    }
+
+    #[test]
+    fn checks_prefixes() {
+        let prefixes = prefix_in(&["34", "37"]);
+        assert!(prefixes.validate("378282246310005"));
+        assert!(!prefixes.validate("3"));
+        let range = prefix_range(2221..=2720);
+        assert!(range.validate("2221000000000009"));
+        assert!(range.validate("2720"));
+        assert!(!range.validate("2721000000000000"));
+        assert!(!range.validate("222"));
+        assert!(!range.validate("22a1"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn prefix_range_ends_have_the_same_length() {
+        prefix_range(4..=49);
+    }
+
+    #[test]
+    fn combines_rules() {
+        let amex = length(15..=15).and(prefix_in(&["34", "37"])).and(luhn());
+        assert_eq!(amex.name(), "length and prefix and luhn");
+        assert!(amex.validate("378282246310005"));
+        assert!(!amex.validate("378282246310006"));
+        assert!(!amex.validate("4111111111111111"));
+
+        let either = prefix_in(&["4"]).or(prefix_range(51..=55)).named("cards");
+        assert_eq!(either.name(), "cards");
+        assert!(either.validate("4"));
+        assert!(either.validate("53"));
+        assert!(!either.validate("56"));
+
+        let boxed: Box<dyn Validator> = Box::new(amex);
+        assert!(boxed.validate("371449635398431"));
+    }
+}
@@ -0,0 +1,1157 @@
+//! Finding card numbers in free text.
+//!
+//! ```
+//! use luhn::scan::{find_candidates, ScanConfig};
+//!
+//! let text = "paid with 4111 1111 1111 1111, ref 12345";
+//! let found = find_candidates(text, &ScanConfig::default());
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(&text[found[0].range.clone()], "4111 1111 1111 1111");
+//! assert_eq!(found[0].digits, "4111111111111111");
+//! ```
+//!
+//! [`redact`] masks whatever it finds, for scrubbing logs:
+//!
+//! ```
+//! use luhn::scan::{redact, ScanConfig};
+//!
+//! let line = "paid with 4111-1111-1111-1111";
+//! assert_eq!(redact(line, &ScanConfig::default()), "paid with ****-****-****-1111");
+//! ```
+//!
+//! [`Scanner`] does the same as [`find_candidates_in_bytes`] for a
+//! reader, without loading it all into memory.
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::io::{self, Read};
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use crate::monitor::Totals;
+use crate::{CancelToken, Monitor, Progress};
+
+/// Controls what [`find_candidates`] looks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanConfig {
+    /// The fewest digits a candidate may have.
+    pub min_len: usize,
+    /// The most digits a candidate may have.
+    pub max_len: usize,
+    /// The ASCII characters which may separate digits, as the dashes do
+    /// in `4111-1111-1111-1111`.  Digits may only be separated by one
+    /// character at a time, and a candidate only ever uses one kind of
+    /// separator.  Leave this empty to only find unbroken runs of digits.
+    pub separators: Vec<u8>,
+    /// Which candidates to report, by their leading digits.
+    pub prefixes: PrefixFilter,
+    /// Which kinds of false positive to discard.
+    pub filters: Filters,
+}
+
+impl Default for ScanConfig {
+    /// Card-number lengths (12 to 19 digits), separated by spaces or
+    /// dashes, with any prefix and no filters.
+    fn default() -> Self {
+        ScanConfig {
+            min_len: 12,
+            max_len: 19,
+            separators: vec![b' ', b'-'],
+            prefixes: PrefixFilter::Any,
+            filters: Filters::default(),
+        }
+    }
+}
+
+/// Filters candidates by their leading digits, such as their BIN.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PrefixFilter {
+    /// Report every candidate.
+    #[default]
+    Any,
+    /// Only report candidates starting with one of these prefixes.
+    Allow(Vec<String>),
+    /// Report every candidate except those starting with one of these
+    /// prefixes.
+    Deny(Vec<String>),
+}
+
+impl PrefixFilter {
+    /// Checks whether candidates with the given digits are reported.
+    pub fn accepts(&self, digits: &str) -> bool {
+        match self {
+            PrefixFilter::Any => true,
+            PrefixFilter::Allow(prefixes) => {
+                prefixes.iter().any(|p| digits.starts_with(p.as_str()))
+            }
+            PrefixFilter::Deny(prefixes) => {
+                !prefixes.iter().any(|p| digits.starts_with(p.as_str()))
+            }
+        }
+    }
+}
+
+/// Heuristics for discarding candidates which pass the Luhn check but
+/// are almost certainly not card numbers.  All are off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Filters {
+    /// Discard candidates whose digits, check digit aside, are all the
+    /// same, like `0000000000000000` or `1111111111111117`.
+    pub repeated_digits: bool,
+    /// Discard candidates whose digits, check digit aside, count up one
+    /// at a time, like `1234567890123452`.
+    pub sequential_digits: bool,
+    /// Discard well-known public test card numbers, like
+    /// `4111111111111111`.
+    pub test_cards: bool,
+    /// Discard candidates shaped like timestamps: Unix times in
+    /// milliseconds, microseconds or nanoseconds (13, 16 or 19 digits
+    /// starting with 1), or `YYYYMMDDhhmmss` followed by fractions of a
+    /// second.
+    pub timestamps: bool,
+    /// Discard candidates which look like phone numbers: those written
+    /// just after a `+`, or after a word like `tel`, `phone` or `fax`.
+    pub phone_numbers: bool,
+}
+
+/// Published test card numbers, which show up wherever payments are
+/// tested.
+const TEST_CARDS: &[&str] = &[
+    "2222420000001113",
+    "2223003122003222",
+    "30569309025904",
+    "3530111333300000",
+    "36227206271667",
+    "3566002020360505",
+    "371449635398431",
+    "378282246310005",
+    "378734493671000",
+    "38520000023237",
+    "4000000000000002",
+    "4000000000000077",
+    "4000000000003220",
+    "4000056655665556",
+    "4012888888881881",
+    "4111111111111111",
+    "4222222222222",
+    "4242424242424242",
+    "4917610000000000003",
+    "5105105105105100",
+    "5200828282828210",
+    "5425233430109903",
+    "5555555555554444",
+    "6011000000000004",
+    "6011000990139424",
+    "6011111111111117",
+    "6200000000000005",
+    "6250941006528599",
+];
+
+/// Words after which a number is taken to be a phone number.
+const PHONE_WORDS: &[&[u8]] = &[
+    b"tel",
+    b"phone",
+    b"telephone",
+    b"fax",
+    b"mobile",
+    b"mob",
+    b"cell",
+    b"cellphone",
+];
+
+/// How many bytes before a candidate the filters look at.
+const LOOKBEHIND: usize = 16;
+
+impl Filters {
+    /// Every filter turned on.
+    pub fn all() -> Self {
+        Filters {
+            repeated_digits: true,
+            sequential_digits: true,
+            test_cards: true,
+            timestamps: true,
+            phone_numbers: true,
+        }
+    }
+
+    /// Checks whether the candidate `digits`, found at `bytes[start..]`,
+    /// should be discarded.
+    fn discards(&self, bytes: &[u8], start: usize, digits: &str) -> bool {
+        let body = &digits.as_bytes()[..digits.len() - 1];
+        (self.repeated_digits && body.windows(2).all(|w| w[0] == w[1]))
+            || (self.sequential_digits
+                && body
+                    .windows(2)
+                    .all(|w| (w[0] - b'0' + 1) % 10 == w[1] - b'0'))
+            || (self.test_cards && TEST_CARDS.contains(&digits))
+            || (self.timestamps && is_timestamp(digits))
+            || (self.phone_numbers
+                && follows_phone_marker(&bytes[start.saturating_sub(LOOKBEHIND)..start]))
+    }
+}
+
+/// Checks whether `digits` looks like a Unix time or a compact date and
+/// time.
+fn is_timestamp(digits: &str) -> bool {
+    if matches!(digits.len(), 13 | 16 | 19) && digits.starts_with('1') {
+        return true;
+    }
+    let field = |range: Range<usize>| digits[range].parse::<u32>().unwrap();
+    digits.len() >= 14
+        && (1970..=2099).contains(&field(0..4))
+        && (1..=12).contains(&field(4..6))
+        && (1..=31).contains(&field(6..8))
+        && field(8..10) <= 23
+        && field(10..12) <= 59
+        && field(12..14) <= 60
+}
+
+/// Checks whether `before`, the text just before a number, marks it as a
+/// phone number.
+fn follows_phone_marker(before: &[u8]) -> bool {
+    let mut end = before.len();
+    // Allow a little punctuation in between, as in `tel: ` or `phone=`.
+    while end > 0
+        && before.len() - end < 3
+        && matches!(before[end - 1], b' ' | b'\t' | b':' | b'=' | b'#' | b'.')
+    {
+        end -= 1;
+    }
+    if before[..end].ends_with(b"+") {
+        return true;
+    }
+    let word_start = before[..end]
+        .iter()
+        .rposition(|b| !b.is_ascii_alphabetic())
+        .map_or(0, |i| i + 1);
+    let word = &before[word_start..end];
+    PHONE_WORDS.iter().any(|w| word.eq_ignore_ascii_case(w))
+}
+
+/// A Luhn-valid run of digits found by [`find_candidates`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Where the run is in the text, separators included.
+    pub range: Range<usize>,
+    /// The run's digits, separators removed.
+    pub digits: String,
+}
+
+/// Finds numbers in `text` which pass the Luhn check.
+///
+/// A candidate is a group of digits, or several joined by single
+/// separators of the same kind, with something other than a digit on
+/// either side: the last sixteen digits of a twenty-digit number don't
+/// count.  Where a run of groups could be read more than one way, such
+/// as `4111111111111111 5555555555554444`, groups are taken from the
+/// left, each starting the longest candidate it can.
+pub fn find_candidates(text: &str, config: &ScanConfig) -> Vec<Match> {
+    find_candidates_in_bytes(text.as_bytes(), config)
+}
+
+/// Like [`find_candidates`], but for text which may not be valid UTF-8,
+/// such as the contents of an arbitrary file.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = bytes.len()))
+)]
+pub fn find_candidates_in_bytes(bytes: &[u8], config: &ScanConfig) -> Vec<Match> {
+    let mut found = Vec::new();
+    let mut i = 0;
+    while let Some(start) = next_digit(bytes, i) {
+        let run = digit_run(bytes, start, &config.separators);
+        i = scan_run(bytes, run, true, config, &mut found);
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(found = found.len(), "scanned");
+    found
+}
+
+/// Like [`find_candidates_in_bytes`], but tells `monitor` how it's
+/// getting on.
+///
+/// If cancelled, returns what it found before then.
+pub fn find_candidates_monitored(
+    bytes: &[u8],
+    config: &ScanConfig,
+    monitor: &Monitor,
+) -> Vec<Match> {
+    scan_monitored(bytes, config, monitor).0
+}
+
+/// Like [`find_candidates_monitored`], also saying whether it was
+/// cancelled.
+pub(crate) fn scan_monitored(
+    bytes: &[u8],
+    config: &ScanConfig,
+    monitor: &Monitor,
+) -> (Vec<Match>, bool) {
+    let mut found = Vec::new();
+    for chunk in split_between_runs(bytes, &config.separators, Monitor::INTERVAL) {
+        if monitor.cancelled() {
+            return (found, true);
+        }
+        let end = chunk.end;
+        found.extend(scan_chunk(bytes, chunk, config));
+        monitor.report(Progress {
+            bytes: end as u64,
+            matches: found.len() as u64,
+        });
+    }
+    (found, false)
+}
+
+/// Like [`find_candidates_in_bytes`], but splits `bytes` into chunks which
+/// are scanned on rayon's thread pool.  The result is the same.
+///
+/// Chunks only ever end between runs, so numbers aren't split between
+/// them.
+#[cfg(feature = "parallel")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = bytes.len()))
+)]
+pub fn find_candidates_parallel(bytes: &[u8], config: &ScanConfig) -> Vec<Match> {
+    find_candidates_in_chunks(bytes, config, Monitor::INTERVAL, &Monitor::new()).0
+}
+
+/// Like [`find_candidates_parallel`], but tells `monitor` how it's getting
+/// on, from rayon's threads.
+///
+/// If cancelled, returns the matches from the chunks up to the first one
+/// which was skipped.
+///
+/// ```
+/// use luhn::scan::{self, ScanConfig};
+/// use luhn::{CancelToken, Monitor};
+///
+/// let token = CancelToken::new();
+/// let cancel = |_| token.cancel();
+/// let monitor = Monitor::new().on_progress(&cancel).cancel_on(&token);
+/// // More chunks than threads, so that some haven't started when the
+/// // first one finishes and cancels the scan.
+/// let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+/// let chunk = format!("4111 1111 1111 1111{}", "x".repeat(Monitor::INTERVAL));
+/// let text = chunk.repeat(threads + 1);
+/// let config = ScanConfig::default();
+/// let found = scan::find_candidates_parallel_monitored(text.as_bytes(), &config, &monitor);
+/// assert!(found.len() <= threads);
+/// ```
+#[cfg(feature = "parallel")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = bytes.len()))
+)]
+pub fn find_candidates_parallel_monitored(
+    bytes: &[u8],
+    config: &ScanConfig,
+    monitor: &Monitor,
+) -> Vec<Match> {
+    find_candidates_in_chunks(bytes, config, Monitor::INTERVAL, monitor).0
+}
+
+/// Scans `bytes` in parallel, returning what's found and whether the scan
+/// was cancelled.  A cancelled scan returns the matches from the chunks
+/// up to the first one it skipped.
+#[cfg(feature = "parallel")]
+pub(crate) fn find_candidates_in_chunks(
+    bytes: &[u8],
+    config: &ScanConfig,
+    chunk_size: usize,
+    monitor: &Monitor,
+) -> (Vec<Match>, bool) {
+    use rayon::prelude::*;
+
+    let totals = Totals::default();
+    let chunks = split_between_runs(bytes, &config.separators, chunk_size);
+    let found: Vec<Option<Vec<Match>>> = chunks
+        .into_par_iter()
+        .map(|chunk| {
+            if monitor.cancelled() {
+                return None;
+            }
+            let len = chunk.len() as u64;
+            let found = scan_chunk(bytes, chunk, config);
+            totals.add(
+                Progress {
+                    bytes: len,
+                    matches: found.len() as u64,
+                },
+                monitor,
+            );
+            Some(found)
+        })
+        .collect();
+    let cancelled = found.iter().any(Option::is_none);
+    let found = found
+        .into_iter()
+        .map_while(|found| found)
+        .flatten()
+        .collect();
+    (found, cancelled)
+}
+
+/// Finds the candidates in `bytes[chunk]`, which doesn't split any runs.
+fn scan_chunk(bytes: &[u8], chunk: Range<usize>, config: &ScanConfig) -> Vec<Match> {
+    // Scanning stops at the chunk's end, but the filters may look at what
+    // comes before its start.
+    let bytes = &bytes[..chunk.end];
+    let mut found = Vec::new();
+    let mut i = chunk.start;
+    while let Some(start) = next_digit(bytes, i) {
+        let run = digit_run(bytes, start, &config.separators);
+        i = scan_run(bytes, run, true, config, &mut found);
+    }
+    found
+}
+
+/// Splits `bytes` into ranges of about `chunk_size` bytes, each ending
+/// just before a byte which is neither a digit nor a separator, so that
+/// no run of digits crosses from one range to the next.  There's always
+/// at least one range, if only an empty one.
+fn split_between_runs(bytes: &[u8], separators: &[u8], chunk_size: usize) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let mut end = (start + chunk_size).min(bytes.len());
+        while end < bytes.len() && (bytes[end].is_ascii_digit() || separators.contains(&bytes[end]))
+        {
+            end += 1;
+        }
+        chunks.push(start..end);
+        start = end;
+        if start == bytes.len() {
+            return chunks;
+        }
+    }
+}
+
+/// Adds the candidates in the run of digits at `bytes[run]` to `found`.
+///
+/// Returns where scanning stopped: the end of the run if it's
+/// `complete`, or else the start of the first group which might yet
+/// begin a candidate, depending on how the run continues.
+fn scan_run(
+    bytes: &[u8],
+    run: Range<usize>,
+    complete: bool,
+    config: &ScanConfig,
+    found: &mut impl Extend<Match>,
+) -> usize {
+    // Most runs of digits in text are too short to bother with.
+    if complete && run.len() < config.min_len {
+        return run.end;
+    }
+    let mut groups = Vec::new();
+    let mut start = run.start;
+    for (i, b) in bytes[run.clone()].iter().enumerate() {
+        if !b.is_ascii_digit() {
+            groups.push(start..run.start + i);
+            start = run.start + i + 1;
+        }
+    }
+    groups.push(start..run.end);
+    let total_digits = run.len() - (groups.len() - 1);
+
+    let mut digits_before = 0;
+    let mut i = 0;
+    while i < groups.len() {
+        // Until more than `max_len` digits follow, a longer candidate
+        // might start here.
+        if !complete && total_digits - digits_before <= config.max_len {
+            return groups[i].start;
+        }
+        let mut longest = None;
+        let mut digits = 0;
+        for (j, group) in groups.iter().enumerate().skip(i) {
+            if j > i + 1 && bytes[group.start - 1] != bytes[groups[i + 1].start - 1] {
+                break;
+            }
+            digits += group.len();
+            if digits > config.max_len {
+                break;
+            }
+            if let Some(m) = candidate(bytes, groups[i].start..group.end, config) {
+                longest = Some((j, m));
+            }
+        }
+        match longest {
+            Some((j, m)) => {
+                // Filter only once the longest candidate's been picked, so
+                // filtering it out doesn't turn up a shorter one instead.
+                if config.prefixes.accepts(&m.digits)
+                    && !config.filters.discards(bytes, m.range.start, &m.digits)
+                {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(number = %crate::masked_prefix(&m.digits), "found candidate");
+                    found.extend(Some(m));
+                }
+                digits_before += groups[i..=j].iter().map(Range::len).sum::<usize>();
+                i = j + 1;
+            }
+            None => {
+                digits_before += groups[i].len();
+                i += 1;
+            }
+        }
+    }
+    run.end
+}
+
+/// Checks whether the digits at `bytes[range]` are a candidate, before
+/// any filtering.
+fn candidate(bytes: &[u8], range: Range<usize>, config: &ScanConfig) -> Option<Match> {
+    let mut len = 0;
+    let mut sum = 0;
+    for &b in bytes[range.clone()].iter().rev() {
+        if b.is_ascii_digit() {
+            let digit = u32::from(b - b'0');
+            sum += if len % 2 == 1 {
+                digit / 5 + (2 * digit) % 10
+            } else {
+                digit
+            };
+            len += 1;
+        }
+    }
+    if !(config.min_len..=config.max_len).contains(&len) || sum % 10 != 0 {
+        return None;
+    }
+    let digits = bytes[range.clone()]
+        .iter()
+        .filter(|b| b.is_ascii_digit())
+        .map(|&b| b as char)
+        .collect();
+    Some(Match { range, digits })
+}
+
+/// Finds the first ASCII digit in `bytes[from..]`.
+///
+/// This looks at eight bytes at a time, since digits tend to be sparse
+/// in the text being scanned.
+fn next_digit(bytes: &[u8], from: usize) -> Option<usize> {
+    const LOW: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGH: u64 = u64::from_ne_bytes([0x80; 8]);
+    const SEVEN_BITS: u64 = u64::from_ne_bytes([0x7f; 8]);
+
+    let mut i = from;
+    while i + 8 <= bytes.len() {
+        let word = u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        // Sets the high bit of each byte between b'0' and b'9'.  No byte
+        // carries or borrows into its neighbour, so the result is exact.
+        let low = word & SEVEN_BITS;
+        let digits = (LOW * (127 + u64::from(b'9') + 1)).wrapping_sub(low)
+            & !word
+            & (low + LOW * (127 - (u64::from(b'0') - 1)))
+            & HIGH;
+        if digits != 0 {
+            return Some(i + digits.trailing_zeros() as usize / 8);
+        }
+        i += 8;
+    }
+    bytes[i.min(bytes.len())..]
+        .iter()
+        .position(u8::is_ascii_digit)
+        .map(|j| i + j)
+}
+
+/// Finds candidates in a reader, as [`find_candidates_in_bytes`] would in
+/// its entire contents.
+///
+/// The reader is read in chunks, and a number split between two chunks is
+/// still found.  Match ranges are offsets from the start of the stream.
+///
+/// ```
+/// use luhn::scan::{ScanConfig, Scanner};
+///
+/// let log: &[u8] = b"ok\npaid with 4111111111111111\nok\n";
+/// let found = Scanner::new(log, ScanConfig::default())
+///     .collect::<std::io::Result<Vec<_>>>()
+///     .unwrap();
+/// assert_eq!(found[0].range, 13..29);
+/// ```
+#[derive(Debug)]
+pub struct Scanner<R> {
+    reader: R,
+    config: ScanConfig,
+    chunk_size: usize,
+    /// Data read but not yet scanned, such as the tail end of a run
+    /// which may continue in the next chunk, after up to `LOOKBEHIND`
+    /// bytes of what came before for the filters to look at.
+    buf: Vec<u8>,
+    /// Where the unscanned data in `buf` starts.
+    scanned: usize,
+    /// The stream offset of `buf[0]`.
+    offset: usize,
+    found: VecDeque<Match>,
+    /// Set when the last chunk ended part way through a group of digits
+    /// too long to start a candidate, so the rest of it must be skipped.
+    skip_digits: bool,
+    eof: bool,
+    cancel: Option<CancelToken>,
+}
+
+impl<R: Read> Scanner<R> {
+    /// Scans `reader`, reading 64 KiB at a time.
+    pub fn new(reader: R, config: ScanConfig) -> Self {
+        Self::with_chunk_size(reader, config, 64 * 1024)
+    }
+
+    /// Scans `reader`, reading `chunk_size` bytes at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(reader: R, config: ScanConfig, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        Scanner {
+            reader,
+            config,
+            chunk_size,
+            buf: Vec::new(),
+            scanned: 0,
+            offset: 0,
+            found: VecDeque::new(),
+            skip_digits: false,
+            eof: false,
+            cancel: None,
+        }
+    }
+
+    /// Stops scanning once `token` is cancelled: the scanner yields what
+    /// it's already found, then ends without reading any more.
+    pub fn cancel_on(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Whether scanning was stopped by cancelling the token passed to
+    /// [`cancel_on`](Self::cancel_on).
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+
+    /// How many bytes have been read from the reader so far, eg. for
+    /// showing progress.
+    pub fn bytes_read(&self) -> u64 {
+        (self.offset + self.buf.len()) as u64
+    }
+
+    /// Reads the next chunk and scans as much of it as possible.
+    fn refill(&mut self) -> io::Result<()> {
+        let len = self.buf.len();
+        self.buf.resize(len + self.chunk_size, 0);
+        let n = loop {
+            match self.reader.read(&mut self.buf[len..]) {
+                Ok(n) => break n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.buf.truncate(len);
+                    return Err(err);
+                }
+            }
+        };
+        self.buf.truncate(len + n);
+        self.eof = n == 0;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(offset = self.offset + len, read = n, "read chunk");
+
+        let mut i = self.scanned;
+        if self.skip_digits {
+            while i < self.buf.len() && self.buf[i].is_ascii_digit() {
+                i += 1;
+            }
+            self.skip_digits = i == self.buf.len();
+        }
+        let mut consumed = i;
+        while i < self.buf.len() {
+            i = match next_digit(&self.buf, i) {
+                Some(start) => start,
+                None => {
+                    consumed = self.buf.len();
+                    break;
+                }
+            };
+            let run = digit_run(&self.buf, i, &self.config.separators);
+            let complete = self.eof || !self.may_continue(&run);
+            let mut found = Vec::new();
+            let end = scan_run(&self.buf, run.clone(), complete, &self.config, &mut found);
+            let offset = self.offset;
+            self.found.extend(found.into_iter().map(|mut m| {
+                m.range = m.range.start + offset..m.range.end + offset;
+                m
+            }));
+            if !complete {
+                if end == run.end {
+                    // Every group has been ruled out, and the last one is
+                    // still going.
+                    self.skip_digits = run.end == self.buf.len();
+                    consumed = run.end;
+                } else {
+                    consumed = end;
+                }
+                break;
+            }
+            i = end;
+            consumed = i;
+        }
+        let drained = consumed.saturating_sub(LOOKBEHIND);
+        self.buf.drain(..drained);
+        self.offset += drained;
+        self.scanned = consumed - drained;
+        Ok(())
+    }
+
+    /// Whether the run at `range` might carry on into the next chunk.
+    fn may_continue(&self, range: &Range<usize>) -> bool {
+        match &self.buf[range.end..] {
+            [] => true,
+            [b] => self.config.separators.contains(b),
+            _ => false,
+        }
+    }
+}
+
+impl<R: Read> Iterator for Scanner<R> {
+    type Item = io::Result<Match>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(m) = self.found.pop_front() {
+                return Some(Ok(m));
+            }
+            if self.eof || self.is_cancelled() {
+                return None;
+            }
+            if let Err(err) = self.refill() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// Masks every candidate in `text`, as found by [`find_candidates`].
+///
+/// All but the last four digits of each candidate become `*`.
+/// Separators are kept, so the result is the same length as `text`.
+/// Text without any candidates is returned as is.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = text.len()))
+)]
+pub fn redact<'a>(text: &'a str, config: &ScanConfig) -> Cow<'a, str> {
+    let found = find_candidates(text, config);
+    if found.is_empty() {
+        return Cow::Borrowed(text);
+    }
+    let mut redacted = text.to_string();
+    for m in found {
+        mask(&mut redacted, m.range);
+    }
+    Cow::Owned(redacted)
+}
+
+/// Like [`redact`], but masks `text` in place.  Returns the number of
+/// candidates masked.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = text.len()))
+)]
+pub fn redact_in_place(text: &mut String, config: &ScanConfig) -> usize {
+    let found = find_candidates(text, config);
+    let count = found.len();
+    for m in found {
+        mask(text, m.range);
+    }
+    count
+}
+
+/// Masks all but the last four digits in `text[range]`.
+fn mask(text: &mut String, range: Range<usize>) {
+    let digits = text[range.clone()]
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .count();
+    let mut to_mask = digits.saturating_sub(4);
+    let masked: String = text[range.clone()]
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_digit() && to_mask > 0 {
+                to_mask -= 1;
+                '*'
+            } else {
+                b as char
+            }
+        })
+        .collect();
+    text.replace_range(range, &masked);
+}
+
+/// Returns the extent of the run of digits starting at `start`: groups
+/// of digits joined by single separators.
+fn digit_run(bytes: &[u8], start: usize, separators: &[u8]) -> Range<usize> {
+    let mut end = start;
+    let mut i = start;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b.is_ascii_digit() {
+            i += 1;
+            end = i;
+        } else if separators.contains(&b) && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<'a>(text: &'a str, config: &ScanConfig) -> Vec<&'a str> {
+        find_candidates(text, config)
+            .into_iter()
+            .map(|m| &text[m.range])
+            .collect()
+    }
+
+    #[test]
+    fn finds_contiguous_and_separated_numbers() {
+        let text = "a=4111111111111111; b=4111-1111-1111-1111. c=3782 822463 10005";
+        assert_eq!(
+            ranges(text, &ScanConfig::default()),
+            [
+                "4111111111111111",
+                "4111-1111-1111-1111",
+                "3782 822463 10005"
+            ]
+        );
+    }
+
+    #[test]
+    fn separators_can_be_disabled() {
+        let config = ScanConfig {
+            separators: Vec::new(),
+            ..ScanConfig::default()
+        };
+        assert_eq!(
+            ranges("4111111111111111 4111-1111-1111-1111", &config),
+            ["4111111111111111"]
+        );
+    }
+
+    #[test]
+    fn splits_runs_into_candidates() {
+        let config = ScanConfig::default();
+        assert_eq!(
+            ranges(
+                "4111111111111111 5555555555554444 12 378282246310005",
+                &config
+            ),
+            ["4111111111111111", "5555555555554444", "378282246310005"]
+        );
+        assert_eq!(
+            ranges("4111 1111 1111 1111-5555-5555-5555-4444", &config),
+            ["4111 1111 1111 1111", "5555-5555-5555-4444"]
+        );
+    }
+
+    #[test]
+    fn filters() {
+        let filtered = |filters: Filters, text: &str| {
+            let config = ScanConfig {
+                filters,
+                ..ScanConfig::default()
+            };
+            assert_eq!(ranges(text, &ScanConfig::default()).len(), 1, "{:?}", text);
+            ranges(text, &config).is_empty()
+        };
+        let only = |set: fn(&mut Filters)| {
+            let mut filters = Filters::default();
+            set(&mut filters);
+            filters
+        };
+
+        let repeated = only(|f| f.repeated_digits = true);
+        assert!(filtered(repeated, "1111111111111117"));
+        assert!(!filtered(repeated, "5555555555554444"));
+
+        let sequential = only(|f| f.sequential_digits = true);
+        assert!(filtered(sequential, "1234567890123452"));
+        assert!(filtered(sequential, "5678901234565"));
+
+        let test_cards = only(|f| f.test_cards = true);
+        assert!(filtered(test_cards, "4242 4242 4242 4242"));
+        assert!(!filtered(test_cards, "4000000000000010"));
+
+        let timestamps = only(|f| f.timestamps = true);
+        assert!(filtered(timestamps, "at 1697300000009 ms"));
+        assert!(filtered(timestamps, "20231014123045122"));
+        assert!(!filtered(timestamps, "4111111111111111"));
+
+        let phones = only(|f| f.phone_numbers = true);
+        assert!(filtered(phones, "call +447911123456"));
+        assert!(filtered(phones, "Tel: 447911123456"));
+        assert!(filtered(phones, "mobile=447911123456"));
+        assert!(!filtered(phones, "card 447911123456"));
+        assert!(filtered(phones, "Telephone 447911123456"));
+        // Only whole words count.
+        assert!(!filtered(phones, "hotel 4111 1111 1111 1111"));
+        assert!(!filtered(phones, "motel: 447911123456"));
+        assert!(!filtered(
+            phones,
+            "telephone bill paid with card 447911123456"
+        ));
+    }
+
+    #[test]
+    fn other_separators() {
+        let config = ScanConfig {
+            separators: b"./".to_vec(),
+            ..ScanConfig::default()
+        };
+        assert_eq!(
+            ranges(
+                "4111.1111.1111.1111 4111/1111/1111/1111 4111-1111-1111-1111",
+                &config
+            ),
+            ["4111.1111.1111.1111", "4111/1111/1111/1111"]
+        );
+    }
+
+    #[test]
+    fn filters_by_prefix() {
+        let text = "4111111111111111 5555555555554444 378282246310005";
+        let allow = ScanConfig {
+            prefixes: PrefixFilter::Allow(vec!["4".into(), "37".into()]),
+            ..ScanConfig::default()
+        };
+        assert_eq!(
+            ranges(text, &allow),
+            ["4111111111111111", "378282246310005"]
+        );
+        let deny = ScanConfig {
+            prefixes: PrefixFilter::Deny(vec!["411111".into()]),
+            ..ScanConfig::default()
+        };
+        assert_eq!(ranges(text, &deny), ["5555555555554444", "378282246310005"]);
+    }
+
+    #[test]
+    fn separators_must_be_single_and_consistent() {
+        let config = ScanConfig::default();
+        assert!(ranges("4111-1111 1111-1111", &config).is_empty());
+        assert!(ranges("4111  1111  1111  1111", &config).is_empty());
+        // A trailing separator isn't part of the match.
+        assert_eq!(
+            ranges("4111-1111-1111-1111- x", &config),
+            ["4111-1111-1111-1111"]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_check_digits_and_lengths() {
+        let config = ScanConfig::default();
+        assert!(ranges("4111111111111112", &config).is_empty());
+        assert!(ranges("79927398713", &config).is_empty());
+        assert!(ranges("94111111111111111111", &config).is_empty());
+        let short = ScanConfig {
+            min_len: 11,
+            ..config
+        };
+        assert_eq!(ranges("id 79927398713.", &short), ["79927398713"]);
+    }
+
+    #[test]
+    fn redacts() {
+        let config = ScanConfig::default();
+        let text = "a=4111111111111111 b=3782 822463 10005 c=4111111111111112";
+        assert_eq!(
+            redact(text, &config),
+            "a=************1111 b=**** ****** *0005 c=4111111111111112"
+        );
+        assert!(matches!(redact("nothing here", &config), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn redacts_in_place() {
+        let mut text = "café 4111111111111111, 4111111111111111".to_string();
+        assert_eq!(redact_in_place(&mut text, &ScanConfig::default()), 2);
+        assert_eq!(text, "café ************1111, ************1111");
+    }
+
+    /// Texts with numbers which are awkward to find in chunks.
+    fn awkward_texts() -> Vec<String> {
+        let mut texts = vec![
+            "a=4111111111111111; b=4111-1111-1111-1111. c=3782 822463 10005".to_string(),
+            "4111111111111111".to_string(),
+            "4111111111111111-".to_string(),
+            "4111-1111 1111-1111 4111111111111111".to_string(),
+            "x 4111 1111 1111 1111 y".to_string(),
+            "4111111111111111 5555555555554444 378282246310005".to_string(),
+            "1 2 3 4111 1111 1111 1111-5555-5555-5555-4444".to_string(),
+        ];
+        // Over-long runs, which the scanner skips rather than buffers.
+        texts.push(format!("{} 4111111111111111", "1".repeat(40)));
+        texts.push(format!("{}-4111 1111 1111 1111", "1-".repeat(20)));
+        texts.push(format!("{}4111111111111111", "12 ".repeat(20)));
+        texts.push("+4111111111111111 tel: 4111111111111111 phone4111111111111111".to_string());
+        texts
+    }
+
+    fn chunking_configs() -> [ScanConfig; 3] {
+        [
+            ScanConfig::default(),
+            ScanConfig {
+                separators: Vec::new(),
+                ..ScanConfig::default()
+            },
+            ScanConfig {
+                filters: Filters {
+                    phone_numbers: true,
+                    ..Filters::default()
+                },
+                ..ScanConfig::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn scanner_matches_find_candidates_at_any_chunk_size() {
+        for config in chunking_configs().iter() {
+            for text in &awkward_texts() {
+                let expected = find_candidates(text, config);
+                for chunk_size in 1..=text.len() {
+                    let found =
+                        Scanner::with_chunk_size(text.as_bytes(), config.clone(), chunk_size)
+                            .collect::<io::Result<Vec<_>>>()
+                            .unwrap();
+                    assert_eq!(found, expected, "{:?} in chunks of {}", text, chunk_size);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_find_candidates_at_any_chunk_size() {
+        for config in chunking_configs().iter() {
+            for text in &awkward_texts() {
+                let expected = find_candidates(text, config);
+                assert_eq!(find_candidates_parallel(text.as_bytes(), config), expected);
+                for chunk_size in 1..=text.len() {
+                    let (found, _) = find_candidates_in_chunks(
+                        text.as_bytes(),
+                        config,
+                        chunk_size,
+                        &Monitor::new(),
+                    );
+                    assert_eq!(found, expected, "{:?} in chunks of {}", text, chunk_size);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scanner_passes_on_errors() {
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+        let mut scanner = Scanner::new(Broken, ScanConfig::default());
+        assert_eq!(scanner.next().unwrap().unwrap_err().to_string(), "broken");
+    }
+
+    #[test]
+    fn next_digit_agrees_with_a_byte_at_a_time() {
+        let bytes: Vec<u8> = (0..=255).chain(b"abc/:9".iter().copied()).collect();
+        for from in 0..bytes.len() {
+            for end in from..=bytes.len() {
+                let expected = bytes[from..end]
+                    .iter()
+                    .position(u8::is_ascii_digit)
+                    .map(|i| from + i);
+                assert_eq!(next_digit(&bytes[..end], from), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reports_progress() {
+        use std::sync::Mutex;
+
+        let text = format!("{}4111111111111111 ", "x".repeat(Monitor::INTERVAL - 4)).repeat(3);
+        let reports = Mutex::new(Vec::new());
+        let record = |progress| reports.lock().unwrap().push(progress);
+        let monitor = Monitor::new().on_progress(&record);
+        let found = find_candidates_monitored(text.as_bytes(), &ScanConfig::default(), &monitor);
+        assert_eq!(found, find_candidates(&text, &ScanConfig::default()));
+        let reports = reports.into_inner().unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].matches, 1);
+        assert_eq!(
+            reports.last(),
+            Some(&Progress {
+                bytes: text.len() as u64,
+                matches: 3
+            })
+        );
+
+        let reports = Mutex::new(Vec::new());
+        let record = |progress| reports.lock().unwrap().push(progress);
+        find_candidates_monitored(
+            b"",
+            &ScanConfig::default(),
+            &Monitor::new().on_progress(&record),
+        );
+        assert_eq!(reports.into_inner().unwrap(), [Progress::default()]);
+    }
+
+    #[test]
+    fn scanner_counts_bytes_read() {
+        let log: &[u8] = b"ok\npaid with 4111111111111111\nok\n";
+        let mut scanner = Scanner::with_chunk_size(log, ScanConfig::default(), 8);
+        assert_eq!(scanner.bytes_read(), 0);
+        scanner.next().unwrap().unwrap();
+        assert_eq!(scanner.bytes_read(), 32);
+        assert!(scanner.next().is_none());
+        assert_eq!(scanner.bytes_read(), log.len() as u64);
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let token = CancelToken::new();
+        let text = "4111111111111111 ".repeat(10);
+        let mut scanner = Scanner::with_chunk_size(text.as_bytes(), ScanConfig::default(), 20)
+            .cancel_on(token.clone());
+        assert!(scanner.next().is_some());
+        token.cancel();
+        assert!(scanner.is_cancelled());
+        // Whatever was found already still comes out.
+        assert!(scanner.by_ref().count() < 9);
+        assert!(scanner.bytes_read() < text.len() as u64);
+
+        let monitor = Monitor::new().cancel_on(&token);
+        assert_eq!(
+            scan_monitored(text.as_bytes(), &ScanConfig::default(), &monitor),
+            (vec![], true)
+        );
+        #[cfg(feature = "parallel")]
+        assert_eq!(
+            find_candidates_in_chunks(text.as_bytes(), &ScanConfig::default(), 20, &monitor),
+            (vec![], true)
+        );
+    }
+
+    #[test]
+    fn ranges_are_byte_offsets() {
+        let text = "café 4111111111111111";
+        let found = find_candidates(text, &ScanConfig::default());
+        assert_eq!(found[0].range, 6..22);
+    }
+}
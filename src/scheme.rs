@@ -0,0 +1,336 @@
+//! Check digit schemes behind a common, object-safe trait.
+
+use crate::rules::{And, Named, Or};
+use crate::tracking::{ups, usps_impb};
+use crate::{
+    br, ch_esr, fi_reference, gs1, iata, medicare_au, mod_n, mpan, nif, no_id, npi, oib, pesel,
+    sscc, tckn, uic, validate_with, vin, Algorithm, Imei, Isin, ValidateConfig,
+};
+
+/// A check digit scheme, built in or not.
+///
+/// The trait is object-safe, so schemes can be kept together as
+/// `Box<dyn Validator>` or `&dyn Validator`, eg. to find which ones an
+/// unknown identifier could belong to:
+///
+/// ```
+/// use luhn::{Algorithm, Validator};
+///
+/// /// An in-house scheme: the digits must sum to a multiple of 7.
+/// struct Sevens;
+///
+/// impl Validator for Sevens {
+///     fn name(&self) -> &str {
+///         "sevens"
+///     }
+///
+///     fn validate(&self, input: &str) -> bool {
+///         !input.is_empty()
+///             && input.bytes().all(|b| b.is_ascii_digit())
+///             && input.bytes().map(|b| u32::from(b - b'0')).sum::<u32>() % 7 == 0
+///     }
+/// }
+///
+/// let schemes: Vec<Box<dyn Validator>> = vec![
+///     Box::new(Algorithm::Luhn),
+///     Box::new(Algorithm::Damm),
+///     Box::new(Sevens),
+/// ];
+/// let matches: Vec<&str> = schemes
+///     .iter()
+///     .filter(|scheme| scheme.validate("5724"))
+///     .map(|scheme| scheme.name())
+///     .collect();
+/// assert_eq!(matches, ["damm"]);
+/// ```
+pub trait Validator {
+    /// A short, lowercase name for the scheme, eg. `"luhn"`.
+    fn name(&self) -> &str;
+
+    /// Whether `input` is well-formed and ends in a good check digit.
+    fn validate(&self, input: &str) -> bool;
+
+    /// A validator accepting input which both `self` and `other` accept.
+    ///
+    /// Its name joins theirs with `" and "`; see [`named`](Validator::named)
+    /// to choose another.
+    fn and<V: Validator>(self, other: V) -> And<Self, V>
+    where
+        Self: Sized,
+    {
+        And::new(self, other)
+    }
+
+    /// A validator accepting input which either `self` or `other` accepts.
+    fn or<V: Validator>(self, other: V) -> Or<Self, V>
+    where
+        Self: Sized,
+    {
+        Or::new(self, other)
+    }
+
+    /// This validator, renamed to `name`.
+    fn named(self, name: impl Into<String>) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        Named::new(self, name.into())
+    }
+}
+
+impl Validator for Algorithm {
+    fn name(&self) -> &str {
+        match self {
+            Algorithm::Luhn => "luhn",
+            Algorithm::Verhoeff => "verhoeff",
+            Algorithm::Damm => "damm",
+            Algorithm::Gs1 => "gs1",
+            Algorithm::Iso7064Mod11_10 => "iso7064-mod-11-10",
+            Algorithm::Iso7064Mod97_10 => "iso7064-mod-97-10",
+        }
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        Algorithm::validate(self, input)
+    }
+}
+
+impl<V: Validator + ?Sized> Validator for &V {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        (**self).validate(input)
+    }
+}
+
+impl<V: Validator + ?Sized> Validator for Box<V> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        (**self).validate(input)
+    }
+}
+
+/// A built-in scheme, as found by [`scheme`].
+struct Builtin {
+    name: &'static str,
+    valid: fn(&str) -> bool,
+}
+
+impl Validator for Builtin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        (self.valid)(input)
+    }
+}
+
+static BUILTINS: [Builtin; 32] = [
+    Builtin {
+        name: "luhn",
+        valid: |s| Algorithm::Luhn.validate(s),
+    },
+    Builtin {
+        name: "verhoeff",
+        valid: |s| Algorithm::Verhoeff.validate(s),
+    },
+    Builtin {
+        name: "damm",
+        valid: |s| Algorithm::Damm.validate(s),
+    },
+    Builtin {
+        name: "gs1",
+        valid: |s| Algorithm::Gs1.validate(s),
+    },
+    Builtin {
+        name: "iso7064-mod-11-10",
+        valid: |s| Algorithm::Iso7064Mod11_10.validate(s),
+    },
+    Builtin {
+        name: "iso7064-mod-97-10",
+        valid: |s| Algorithm::Iso7064Mod97_10.validate(s),
+    },
+    Builtin {
+        name: "luhn-hex",
+        valid: mod_n::valid_hex,
+    },
+    Builtin {
+        name: "luhn-crockford",
+        valid: mod_n::valid_crockford,
+    },
+    Builtin {
+        name: "luhn-base62",
+        valid: mod_n::valid_base62,
+    },
+    Builtin {
+        name: "luhn-leading",
+        valid: crate::valid_leading_check,
+    },
+    Builtin {
+        name: "pan",
+        valid: |s| validate_with(s, &ValidateConfig::PAN).is_ok(),
+    },
+    Builtin {
+        name: "isin",
+        valid: |s| Isin::validate(s).is_ok(),
+    },
+    Builtin {
+        name: "imei",
+        valid: |s| Imei::validate(s).is_ok(),
+    },
+    Builtin {
+        name: "gtin",
+        valid: |s| gs1::Gtin::validate(s).is_ok(),
+    },
+    Builtin {
+        name: "sscc",
+        valid: sscc::valid,
+    },
+    Builtin {
+        name: "ups",
+        valid: ups::valid,
+    },
+    Builtin {
+        name: "usps-impb",
+        valid: usps_impb::valid,
+    },
+    Builtin {
+        name: "iata",
+        valid: iata::valid,
+    },
+    Builtin {
+        name: "uic",
+        valid: uic::valid,
+    },
+    Builtin {
+        name: "vin",
+        valid: vin::valid,
+    },
+    Builtin {
+        name: "mpan",
+        valid: mpan::valid,
+    },
+    Builtin {
+        name: "npi",
+        valid: npi::valid,
+    },
+    Builtin {
+        name: "medicare-au",
+        valid: medicare_au::valid,
+    },
+    Builtin {
+        name: "cpf",
+        valid: br::cpf_valid,
+    },
+    Builtin {
+        name: "cnpj",
+        valid: br::cnpj_valid,
+    },
+    Builtin {
+        name: "pesel",
+        valid: pesel::valid,
+    },
+    Builtin {
+        name: "tckn",
+        valid: tckn::valid,
+    },
+    Builtin {
+        name: "nif",
+        valid: nif::valid,
+    },
+    Builtin {
+        name: "no-id",
+        valid: no_id::valid,
+    },
+    Builtin {
+        name: "oib",
+        valid: oib::valid,
+    },
+    Builtin {
+        name: "fi-reference",
+        valid: fi_reference::valid,
+    },
+    Builtin {
+        name: "ch-esr",
+        valid: ch_esr::valid,
+    },
+];
+
+/// Looks up a built-in scheme by name, ignoring case, for when the name
+/// comes from user input.
+///
+/// The names are those of the algorithms, as given by
+/// [`Validator::name`], such as `"luhn"` and `"verhoeff"`, and of the
+/// identifier types, such as `"isin"`, `"imei"` and `"npi"`, which
+/// validate as each type's module does.  See [`schemes`] for them all.
+///
+/// ```
+/// let isin = luhn::scheme("ISIN").unwrap();
+/// assert!(isin.validate("US0378331005"));
+/// assert!(luhn::scheme("npi").unwrap().validate("1234567893"));
+/// assert!(luhn::scheme("nonsense").is_none());
+/// ```
+pub fn scheme(name: &str) -> Option<&'static dyn Validator> {
+    schemes().find(|scheme| scheme.name().eq_ignore_ascii_case(name))
+}
+
+/// All the built-in schemes, eg. to find which ones an identifier could
+/// belong to.
+pub fn schemes() -> impl Iterator<Item = &'static dyn Validator> {
+    BUILTINS.iter().map(|builtin| builtin as &dyn Validator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithms_are_validators() {
+        let schemes: [&dyn Validator; 3] =
+            [&Algorithm::Luhn, &Algorithm::Verhoeff, &Algorithm::Gs1];
+        let matching = |input| {
+            schemes
+                .iter()
+                .filter(|scheme| scheme.validate(input))
+                .map(|scheme| scheme.name())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(matching("79927398713"), ["luhn"]);
+        assert_eq!(matching("4006381333931"), ["gs1"]);
+        assert_eq!(matching("2363"), ["verhoeff", "gs1"]);
+        assert_eq!(matching("123451"), ["verhoeff"]);
+        assert!(matching("").is_empty());
+    }
+
+    #[test]
+    fn finds_schemes_by_name() {
+        for scheme in schemes() {
+            assert_eq!(super::scheme(scheme.name()).unwrap().name(), scheme.name());
+        }
+        for algorithm in [Algorithm::Damm, Algorithm::Iso7064Mod97_10].iter() {
+            assert_eq!(
+                super::scheme(algorithm.name()).unwrap().name(),
+                algorithm.name()
+            );
+        }
+        assert!(super::scheme("IMEI").unwrap().validate("490154203237518"));
+        assert!(super::scheme("pan").unwrap().validate("4111111111111111"));
+        assert!(!super::scheme("pan").unwrap().validate("79927398713"));
+        assert!(super::scheme("").is_none());
+    }
+
+    #[test]
+    fn classifies_identifiers() {
+        let matching: Vec<&str> = schemes()
+            .filter(|scheme| scheme.validate("4111111111111111"))
+            .map(|scheme| scheme.name())
+            .collect();
+        assert_eq!(matching, ["luhn", "iso7064-mod-11-10", "pan"]);
+    }
+}
@@ -0,0 +1,331 @@
+//! Enumerating valid numbers in order.
+
+use crate::checksum;
+
+/// Yields consecutive valid numbers which start with `prefix`.
+///
+/// Each number is `prefix`, followed by a `body_len`-digit body, followed
+/// by the check digit.  The body counts up from all zeros to all nines,
+/// so the iterator yields `10^body_len` numbers in total.  Useful for
+/// allocating blocks of membership or gift-card numbers.  Panics if
+/// `prefix` contains anything other than ASCII digits.
+///
+/// ```
+/// let mut seq = luhn::sequence("42", 2);
+/// assert_eq!(seq.next().as_deref(), Some("42002"));
+/// assert_eq!(seq.next().as_deref(), Some("42010"));
+/// assert_eq!(seq.next().as_deref(), Some("42028"));
+/// ```
+pub fn sequence(prefix: &str, body_len: usize) -> impl Iterator<Item = String> {
+    assert_decimal(prefix);
+    let mut digits = prefix.as_bytes().to_vec();
+    digits.resize(prefix.len() + body_len, b'0');
+    Sequence {
+        digits: Some(digits),
+        prefix_len: prefix.len(),
+    }
+}
+
+/// Returns the `n`th number (counting from zero) yielded by
+/// [`sequence`]`(prefix, body_len)`, without iterating.
+///
+/// Returns `None` if `n` doesn't fit in `body_len` digits.  Handy for
+/// handing out identifiers from a partitioned number space: each
+/// allocator can own a range of indices.  Panics if `prefix` contains
+/// anything other than ASCII digits.
+///
+/// ```
+/// assert_eq!(luhn::nth_valid("42", 2, 2).as_deref(), Some("42028"));
+/// assert_eq!(luhn::nth_valid("42", 2, 100), None);
+/// ```
+pub fn nth_valid(prefix: &str, body_len: usize, n: u64) -> Option<String> {
+    assert_decimal(prefix);
+    if body_len < 20 && n >= 10u64.pow(body_len as u32) {
+        return None;
+    }
+    let mut number = prefix.to_string();
+    if body_len > 0 {
+        number.push_str(&format!("{:0width$}", n, width = body_len));
+    }
+    number.push(checksum(number.as_bytes()) as char);
+    Some(number)
+}
+
+/// Returns the smallest valid number of the same length which is
+/// numerically greater than `n`, or `None` if there isn't one.
+///
+/// Leading zeros are kept, so this steps through fixed-width identifiers.
+/// The digits before the check digit decide it, so each valid number is
+/// roughly ten more than the last.  Panics if `n` contains anything other
+/// than ASCII digits.
+///
+/// ```
+/// assert_eq!(luhn::next_valid("79927398713").as_deref(), Some("79927398721"));
+/// assert_eq!(luhn::next_valid("0000").as_deref(), Some("0018"));
+/// assert_eq!(luhn::next_valid("9999"), None);
+/// ```
+pub fn next_valid(n: &str) -> Option<String> {
+    step(n, |body, check| checksum(body) > check, increment)
+}
+
+/// Returns the largest valid number of the same length which is
+/// numerically less than `n`, or `None` if there isn't one.  The
+/// counterpart of [`next_valid`]; panics under the same conditions.
+///
+/// ```
+/// assert_eq!(luhn::prev_valid("79927398721").as_deref(), Some("79927398713"));
+/// assert_eq!(luhn::prev_valid("0018").as_deref(), Some("0000"));
+/// assert_eq!(luhn::prev_valid("0000"), None);
+/// ```
+pub fn prev_valid(n: &str) -> Option<String> {
+    step(n, |body, check| checksum(body) < check, decrement)
+}
+
+/// Keeps the body of `n` if `keep(body, check_digit)` says its own check
+/// digit lies the right way, and otherwise moves it along with `advance`,
+/// returning the body and its check digit.
+fn step(
+    n: &str,
+    keep: impl Fn(&[u8], u8) -> bool,
+    advance: impl Fn(&mut [u8]) -> bool,
+) -> Option<String> {
+    assert!(
+        n.bytes().all(|b| b.is_ascii_digit()),
+        "Not a decimal number: {:?}",
+        n
+    );
+    let (&check, body) = n.as_bytes().split_last()?;
+    let mut body = body.to_vec();
+    if !keep(&body, check) && !advance(&mut body) {
+        return None;
+    }
+    body.push(checksum(&body));
+    Some(String::from_utf8(body).unwrap())
+}
+
+/// Adds one to `digits`, returning false if they were all nines.
+fn increment(digits: &mut [u8]) -> bool {
+    for d in digits.iter_mut().rev() {
+        if *d == b'9' {
+            *d = b'0';
+        } else {
+            *d += 1;
+            return true;
+        }
+    }
+    false
+}
+
+/// Takes one from `digits`, returning false if they were all zeros.
+fn decrement(digits: &mut [u8]) -> bool {
+    for d in digits.iter_mut().rev() {
+        if *d == b'0' {
+            *d = b'9';
+        } else {
+            *d -= 1;
+            return true;
+        }
+    }
+    false
+}
+
+/// Counts the Luhn-valid integers in `start..=end`.
+///
+/// Runs in time proportional to the number of digits, not the size of
+/// the range: every block of ten consecutive integers `10q..=10q+9`
+/// contains exactly one valid number.
+///
+/// ```
+/// assert_eq!(luhn::count_valid_in_range(0, 99), 10);
+/// assert_eq!(luhn::count_valid_in_range(0, u64::MAX), u64::MAX / 10 + 1);
+/// ```
+pub fn count_valid_in_range(start: u64, end: u64) -> u64 {
+    if start > end {
+        return 0;
+    }
+    let end_is_valid = check_digit(end / 10) == end % 10;
+    count_valid_below(end) - count_valid_below(start) + end_is_valid as u64
+}
+
+/// Yields the Luhn-valid integers in `start..=end`, in order.
+///
+/// Each block of ten consecutive integers `10q..=10q+9` holds exactly one
+/// valid number, `10q` plus the check digit for `q`, so this takes one
+/// step per valid number rather than testing every integer in the range.
+///
+/// ```
+/// let valid: Vec<u64> = luhn::valid_range(100, 150).collect();
+/// assert_eq!(valid, [109, 117, 125, 133, 141]);
+/// assert_eq!(luhn::valid_range(0, u64::MAX).next(), Some(0));
+/// ```
+pub fn valid_range(start: u64, end: u64) -> impl Iterator<Item = u64> {
+    (start / 10..=end / 10).filter_map(move |q| {
+        // Only the last block can overflow.
+        let n = (q * 10).checked_add(check_digit(q))?;
+        (start..=end).contains(&n).then_some(n)
+    })
+}
+
+/// Counts the Luhn-valid integers in `0..n`.
+fn count_valid_below(n: u64) -> u64 {
+    let (q, r) = (n / 10, n % 10);
+    q + (check_digit(q) < r) as u64
+}
+
+/// The Luhn check digit to append to the decimal digits of `n`.
+///
+/// Leading zeros don't change the Luhn sum, so neither does the width.
+pub(crate) fn check_digit(mut n: u64) -> u64 {
+    let mut sum = 0;
+    let mut double = true;
+    while n > 0 {
+        let d = n % 10;
+        sum += if double { d / 5 + (2 * d) % 10 } else { d };
+        double = !double;
+        n /= 10;
+    }
+    (10 - sum % 10) % 10
+}
+
+fn assert_decimal(prefix: &str) {
+    assert!(
+        prefix.bytes().all(|b| b.is_ascii_digit()),
+        "Not a decimal prefix: {:?}",
+        prefix
+    );
+}
+
+struct Sequence {
+    /// The next number to yield, without its check digit.  `None` once
+    /// the body has overflowed.
+    digits: Option<Vec<u8>>,
+    prefix_len: usize,
+}
+
+impl Iterator for Sequence {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let digits = self.digits.as_mut()?;
+        let mut number = digits.clone();
+        number.push(checksum(digits));
+
+        if !increment(&mut digits[self.prefix_len..]) {
+            self.digits = None;
+        }
+
+        Some(String::from_utf8(number).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valid;
+
+    #[test]
+    fn sequence_yields_every_body() {
+        let numbers: Vec<String> = sequence("9", 2).collect();
+        assert_eq!(numbers.len(), 100);
+        assert_eq!(numbers[0], "9001");
+        assert_eq!(numbers[99], "9993");
+        for (i, n) in numbers.iter().enumerate() {
+            assert!(valid(n), "{} doesn't validate", n);
+            assert_eq!(n[1..3].parse::<usize>().unwrap(), i);
+        }
+    }
+
+    #[test]
+    fn nth_valid_agrees_with_sequence() {
+        for (i, n) in sequence("1234", 3).enumerate() {
+            assert_eq!(nth_valid("1234", 3, i as u64), Some(n));
+        }
+        assert_eq!(nth_valid("1234", 3, 1000), None);
+        assert_eq!(nth_valid("", 0, 0).as_deref(), Some("0"));
+        assert_eq!(nth_valid("", 0, 1), None);
+        assert!(nth_valid("", 25, u64::MAX).is_some());
+    }
+
+    #[test]
+    fn count_valid_in_range_agrees_with_brute_force() {
+        let brute = |start: u64, end: u64| (start..=end).filter(|n| valid(&n.to_string())).count();
+        for &(start, end) in &[
+            (0, 0),
+            (0, 9),
+            (1, 9),
+            (5, 17),
+            (18, 18),
+            (123, 4567),
+            (79927398713, 79927398799),
+        ] {
+            assert_eq!(
+                count_valid_in_range(start, end) as usize,
+                brute(start, end),
+                "{}..={}",
+                start,
+                end
+            );
+        }
+        assert_eq!(count_valid_in_range(10, 9), 0);
+        assert_eq!(count_valid_in_range(u64::MAX, u64::MAX), 0);
+    }
+
+    #[test]
+    fn next_and_prev_valid_agree_with_brute_force() {
+        let numbers: Vec<String> = (0..1000).map(|n| format!("{:03}", n)).collect();
+        for (i, n) in numbers.iter().enumerate() {
+            let next = numbers[i + 1..].iter().find(|n| valid(n));
+            let prev = numbers[..i].iter().rev().find(|n| valid(n));
+            assert_eq!(next_valid(n).as_ref(), next, "after {}", n);
+            assert_eq!(prev_valid(n).as_ref(), prev, "before {}", n);
+        }
+        assert_eq!(next_valid(""), None);
+        assert_eq!(next_valid("5"), None);
+        assert_eq!(prev_valid("5").as_deref(), Some("0"));
+        assert_eq!(
+            next_valid("99999999999999999999999999999990").as_deref(),
+            Some("99999999999999999999999999999991")
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn next_valid_needs_digits() {
+        next_valid("4111-1111");
+    }
+
+    #[test]
+    fn valid_range_agrees_with_brute_force() {
+        for &(start, end) in &[
+            (0, 0),
+            (0, 9),
+            (1, 9),
+            (5, 17),
+            (18, 18),
+            (123, 4567),
+            (79927398713, 79927398799),
+            (10, 9),
+        ] {
+            let brute: Vec<u64> = (start..=end).filter(|n| valid(&n.to_string())).collect();
+            assert_eq!(
+                valid_range(start, end).collect::<Vec<_>>(),
+                brute,
+                "{}..={}",
+                start,
+                end
+            );
+            assert_eq!(brute.len() as u64, count_valid_in_range(start, end));
+        }
+        let top: Vec<u64> = valid_range(u64::MAX - 30, u64::MAX).collect();
+        let brute: Vec<u64> = (u64::MAX - 30..=u64::MAX)
+            .filter(|n| valid(&n.to_string()))
+            .collect();
+        assert_eq!(top, brute);
+    }
+
+    #[test]
+    fn empty_body_yields_once() {
+        let numbers: Vec<String> = sequence("7992739871", 0).collect();
+        assert_eq!(numbers, vec!["79927398713"]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Helpers for validating while deserializing with serde.
+//!
+//! [`Pan`], [`Isin`] and [`Imei`] implement `Serialize` and `Deserialize`
+//! directly: they serialize as plain strings, and are validated when
+//! deserialized.  For fields which are plain `String`s, use the
+//! functions in this module.
+//!
+//! ```
+//! #[derive(serde::Deserialize)]
+//! struct Payment {
+//!     #[serde(deserialize_with = "luhn::serde::validated")]
+//!     card_number: String,
+//! }
+//!
+//! let json = r#"{ "card_number": "4111111111111112" }"#;
+//! let err = serde_json::from_str::<Payment>(json).err().unwrap();
+//! assert_eq!(
+//!     err.to_string(),
+//!     "bad check digit '2' at position 15, expected '1' at line 1 column 37",
+//! );
+//! ```
+
+use ::serde::de::{Deserialize, Deserializer, Error};
+use ::serde::{Serialize, Serializer};
+
+use crate::{check_digits, Imei, Isin, Pan};
+
+/// Deserializes a string of decimal digits, failing unless it validates.
+///
+/// Use it as `#[serde(deserialize_with = "luhn::serde::validated")]`.
+pub fn validated<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    check_digits(&s).map_err(D::Error::custom)?;
+    Ok(s)
+}
+
+/// Like [`validated`], but for `Option<String>` fields.
+///
+/// Use it as `#[serde(default, deserialize_with = "luhn::serde::validated_option")]`.
+pub fn validated_option<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let s = Option::<String>::deserialize(deserializer)?;
+    if let Some(s) = &s {
+        check_digits(s).map_err(D::Error::custom)?;
+    }
+    Ok(s)
+}
+
+macro_rules! impl_serde {
+    ($($t:ty),*) => {$(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                <$t>::new(&s).map_err(D::Error::custom)
+            }
+        }
+    )*};
+}
+
+impl_serde!(Pan, Isin, Imei);
+
+#[cfg(test)]
+mod tests {
+    use ::serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Account {
+        #[serde(deserialize_with = "crate::serde::validated")]
+        number: String,
+        #[serde(default, deserialize_with = "crate::serde::validated_option")]
+        backup: Option<String>,
+    }
+
+    #[test]
+    fn accepts_valid_strings() {
+        let account: Account = serde_json::from_str(r#"{ "number": "79927398713" }"#).unwrap();
+        assert_eq!(account.number, "79927398713");
+        assert_eq!(account.backup, None);
+    }
+
+    #[test]
+    fn newtypes_round_trip() {
+        let pan: crate::Pan = serde_json::from_str(r#""4111111111111111""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&pan).unwrap(),
+            r#""4111111111111111""#
+        );
+        let isin: crate::Isin = serde_json::from_str(r#""US0378331005""#).unwrap();
+        assert_eq!(serde_json::to_string(&isin).unwrap(), r#""US0378331005""#);
+        let imei: crate::Imei = serde_json::from_str(r#""490154203237518""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&imei).unwrap(),
+            r#""490154203237518""#
+        );
+    }
+
+    #[test]
+    fn newtypes_validate_on_deserialize() {
+        let err = serde_json::from_str::<crate::Pan>(r#""41111111""#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("too short: 8 characters, expected at least 12"));
+        assert!(serde_json::from_str::<crate::Isin>(r#""US0378331000""#).is_err());
+        assert!(serde_json::from_str::<crate::Imei>(r#""490154203237519""#).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_strings() {
+        let err = serde_json::from_str::<Account>(r#"{ "number": "799x7398713" }"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid character 'x' at position 3"));
+        let json = r#"{ "number": "79927398713", "backup": "79927398710" }"#;
+        let err = serde_json::from_str::<Account>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("bad check digit '0' at position 10, expected '3'"));
+    }
+}
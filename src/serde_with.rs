@@ -0,0 +1,70 @@
+//! Adapters for [serde_with](https://docs.rs/serde_with).
+//!
+//! ```
+//! use serde_with::serde_as;
+//!
+//! #[serde_as]
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Payment {
+//!     #[serde_as(as = "luhn::serde_with::LuhnValidated")]
+//!     card_number: String,
+//!     #[serde_as(as = "Option<luhn::serde_with::LuhnValidated>")]
+//!     backup_card_number: Option<String>,
+//! }
+//!
+//! let json = r#"{ "card_number": "4111111111111112", "backup_card_number": null }"#;
+//! assert!(serde_json::from_str::<Payment>(json).is_err());
+//! ```
+
+use ::serde::de::{Deserialize, Deserializer, Error};
+use ::serde::Serializer;
+use ::serde_with::{DeserializeAs, SerializeAs};
+
+use crate::check_digits;
+
+/// Serializes strings as-is, and deserializes them only if they validate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LuhnValidated;
+
+impl<'de> DeserializeAs<'de, String> for LuhnValidated {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        check_digits(&s).map_err(D::Error::custom)?;
+        Ok(s)
+    }
+}
+
+impl<T: AsRef<str>> SerializeAs<T> for LuhnValidated {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(source.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+    use ::serde_with::serde_as;
+
+    use super::*;
+
+    #[serde_as]
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Cards {
+        #[serde_as(as = "Vec<LuhnValidated>")]
+        numbers: Vec<String>,
+    }
+
+    #[test]
+    fn validates_every_element() {
+        let json = r#"{"numbers":["4111111111111111","79927398713"]}"#;
+        let cards: Cards = serde_json::from_str(json).unwrap();
+        assert_eq!(cards.numbers, vec!["4111111111111111", "79927398713"]);
+        assert_eq!(serde_json::to_string(&cards).unwrap(), json);
+
+        let json = r#"{"numbers":["4111111111111111","79927398710"]}"#;
+        let err = serde_json::from_str::<Cards>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("bad check digit '0' at position 10, expected '3'"));
+    }
+}
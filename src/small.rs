@@ -0,0 +1,88 @@
+//! A string buffer which keeps inputs of up to [`SMALL`] bytes on the
+//! stack, for validating typical identifiers without allocating.
+
+use std::iter::FromIterator;
+use std::ops::Deref;
+
+/// The longest input, in bytes, which validation handles without
+/// allocating.  This covers card numbers, ISINs, IMEIs and the other
+/// identifiers in this crate.
+pub(crate) const SMALL: usize = 32;
+
+/// A `String` which only allocates once it grows past [`SMALL`] bytes.
+#[derive(Debug)]
+pub(crate) enum SmallString {
+    Stack { buf: [u8; SMALL], len: usize },
+    Heap(String),
+}
+
+impl SmallString {
+    pub(crate) fn new() -> SmallString {
+        SmallString::Stack {
+            buf: [0; SMALL],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, c: char) {
+        let mut utf8 = [0; 4];
+        self.push_str(c.encode_utf8(&mut utf8));
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        match self {
+            SmallString::Stack { buf, len } if *len + s.len() <= SMALL => {
+                buf[*len..*len + s.len()].copy_from_slice(s.as_bytes());
+                *len += s.len();
+            }
+            SmallString::Stack { .. } => {
+                let mut spilled = String::with_capacity(2 * SMALL + s.len());
+                spilled.push_str(self);
+                spilled.push_str(s);
+                *self = SmallString::Heap(spilled);
+            }
+            SmallString::Heap(string) => string.push_str(s),
+        }
+    }
+}
+
+impl Deref for SmallString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            // Only whole UTF-8 strings are copied in.
+            SmallString::Stack { buf, len } => std::str::from_utf8(&buf[..*len]).unwrap(),
+            SmallString::Heap(string) => string,
+        }
+    }
+}
+
+impl FromIterator<char> for SmallString {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> SmallString {
+        let mut s = SmallString::new();
+        for c in iter {
+            s.push(c);
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spills_onto_the_heap() {
+        let mut s = SmallString::new();
+        for _ in 0..SMALL {
+            s.push('1');
+        }
+        assert!(matches!(s, SmallString::Stack { .. }));
+        s.push('é');
+        assert!(matches!(s, SmallString::Heap(_)));
+        assert_eq!(&*s, format!("{}é", "1".repeat(SMALL)));
+        let s: SmallString = "12é".chars().collect();
+        assert_eq!(&*s, "12é");
+    }
+}
@@ -0,0 +1,375 @@
+//! Check digit schemes defined by data rather than code.
+//!
+//! A [`SchemeSpec`] describes a weighted-sum scheme: which characters the
+//! body may use, how each is weighted, the modulus, and where the check
+//! character goes.  With the `serde` feature it can be deserialized, so
+//! new identifier types can be added in configuration files.  Build it
+//! into a [`Scheme`] to use it:
+//!
+//! ```
+//! use luhn::spec::{CheckPosition, SchemeSpec};
+//! use luhn::Validator;
+//!
+//! let isbn_10 = SchemeSpec {
+//!     name: "isbn-10".to_string(),
+//!     alphabet: "0123456789".to_string(),
+//!     check_alphabet: Some("0123456789X".to_string()),
+//!     weights: (2..=10).collect(),
+//!     modulus: 11,
+//!     sum_product_digits: false,
+//!     check_position: CheckPosition::Last,
+//!     min_len: 10,
+//!     max_len: Some(10),
+//! }
+//! .build()
+//! .unwrap();
+//! assert!(isbn_10.validate("0306406152"));
+//! assert_eq!(isbn_10.checksum("080442957"), Ok('X'));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{LuhnError, Validator};
+
+/// Where the check character goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum CheckPosition {
+    /// At the end, as in most schemes.
+    #[default]
+    Last,
+    /// At the start.
+    First,
+}
+
+/// The definition of a weighted-sum check digit scheme.
+///
+/// Each character of the body is worth its index in `alphabet`.  Counting
+/// outwards from the check character, values are multiplied by `weights`
+/// in turn, repeating them as needed; the check character, worth its
+/// index in `check_alphabet`, has weight 1.  The check character is the
+/// one which makes the sum of the products a multiple of `modulus`.
+///
+/// Luhn, for instance, has weights `[2, 1]`, modulus 10, and
+/// `sum_product_digits` set.
+///
+/// When deserialized, everything but `name`, `weights` and `modulus` is
+/// optional, defaulting to decimal digits, a trailing check digit and a
+/// length of at least two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct SchemeSpec {
+    /// What to call the scheme, as [`Validator::name`] does.
+    pub name: String,
+    /// The characters the body may contain, in order of value.
+    #[cfg_attr(feature = "serde", serde(default = "decimal"))]
+    pub alphabet: String,
+    /// The characters the check character may be, in order of value, if
+    /// not those of `alphabet`.  Needs at least `modulus` characters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub check_alphabet: Option<String>,
+    pub weights: Vec<u32>,
+    pub modulus: u32,
+    /// Whether to replace each product by the sum of its digits in base
+    /// `modulus` before adding it up, as Luhn does.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sum_product_digits: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub check_position: CheckPosition,
+    /// The least number of characters allowed, counting the check
+    /// character.
+    #[cfg_attr(feature = "serde", serde(default = "min_len"))]
+    pub min_len: usize,
+    /// The most characters allowed, counting the check character, if
+    /// there's a limit.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_len: Option<usize>,
+}
+
+#[cfg(feature = "serde")]
+fn decimal() -> String {
+    "0123456789".to_string()
+}
+
+#[cfg(feature = "serde")]
+fn min_len() -> usize {
+    2
+}
+
+impl SchemeSpec {
+    /// Checks that the definition makes sense, returning a scheme to
+    /// validate with.
+    pub fn build(&self) -> Result<Scheme, SpecError> {
+        let alphabet: Vec<char> = self.alphabet.chars().collect();
+        let check_alphabet: Vec<char> = match &self.check_alphabet {
+            Some(check_alphabet) => check_alphabet.chars().collect(),
+            None => alphabet.clone(),
+        };
+        let unique = |chars: &[char]| {
+            chars
+                .iter()
+                .enumerate()
+                .all(|(i, c)| !chars[..i].contains(c))
+        };
+        if alphabet.is_empty() || !unique(&alphabet) {
+            return Err(SpecError("alphabet must be unique characters"));
+        }
+        if !unique(&check_alphabet) {
+            return Err(SpecError("check alphabet must be unique characters"));
+        }
+        if self.modulus < 2 {
+            return Err(SpecError("modulus must be at least 2"));
+        }
+        if check_alphabet.len() < self.modulus as usize {
+            return Err(SpecError(
+                "check alphabet needs a character for each value below the modulus",
+            ));
+        }
+        if self.weights.is_empty() {
+            return Err(SpecError("weights must not be empty"));
+        }
+        if self.min_len == 0 || self.max_len.is_some_and(|max| max < self.min_len) {
+            return Err(SpecError("length bounds must be nonzero and in order"));
+        }
+        Ok(Scheme {
+            spec: self.clone(),
+            alphabet,
+            check_alphabet,
+        })
+    }
+}
+
+/// A [`SchemeSpec`] which makes sense; see [`SchemeSpec::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheme {
+    spec: SchemeSpec,
+    alphabet: Vec<char>,
+    check_alphabet: Vec<char>,
+}
+
+impl Scheme {
+    /// The definition this scheme was built from.
+    pub fn spec(&self) -> &SchemeSpec {
+        &self.spec
+    }
+
+    /// Computes the check character for `body`.
+    ///
+    /// Fails with [`LuhnError::InvalidCharacter`] if `body` has characters
+    /// outside the alphabet.  Length bounds aren't checked.
+    pub fn checksum(&self, body: &str) -> Result<char, LuhnError> {
+        self.check_body(body, 0)?;
+        let sum = match self.spec.check_position {
+            CheckPosition::Last => self.sum(body.chars().rev()),
+            CheckPosition::First => self.sum(body.chars()),
+        };
+        let modulus = u64::from(self.spec.modulus);
+        let check = (modulus - sum) % modulus;
+        Ok(self.check_alphabet[check as usize])
+    }
+
+    /// The weighted sum, mod the modulus, of `outwards`: the characters of
+    /// a body in the alphabet, starting next to the check character.
+    fn sum(&self, outwards: impl Iterator<Item = char>) -> u64 {
+        let modulus = u64::from(self.spec.modulus);
+        outwards
+            // The body has been checked against the alphabet.
+            .filter_map(|c| self.value(c))
+            .zip(self.spec.weights.iter().cycle())
+            .fold(0, |sum, (value, &weight)| {
+                let mut product = u64::from(value) * u64::from(weight);
+                if self.spec.sum_product_digits {
+                    let mut digits = 0;
+                    while product > 0 {
+                        digits += product % modulus;
+                        product /= modulus;
+                    }
+                    product = digits;
+                }
+                (sum + product) % modulus
+            })
+    }
+
+    /// Like [`Validator::validate`], but says what's wrong.
+    pub fn check(&self, s: &str) -> Result<(), LuhnError> {
+        let len = s.chars().count();
+        if len < self.spec.min_len {
+            return Err(LuhnError::TooShort {
+                len,
+                min: self.spec.min_len,
+            });
+        }
+        if let Some(max) = self.spec.max_len.filter(|&max| len > max) {
+            return Err(LuhnError::TooLong { len, max });
+        }
+        let ((position, found), body) = match self.spec.check_position {
+            CheckPosition::Last => {
+                let (position, found) = s.char_indices().next_back().unwrap();
+                ((position, found), &s[..position])
+            }
+            CheckPosition::First => {
+                let found = s.chars().next().unwrap();
+                ((0, found), &s[found.len_utf8()..])
+            }
+        };
+        let offset = if position == 0 { found.len_utf8() } else { 0 };
+        self.check_body(body, offset)?;
+        if !self.check_alphabet.contains(&found) {
+            return Err(LuhnError::InvalidCharacter {
+                position,
+                character: found,
+            });
+        }
+        let expected = self.checksum(body)?;
+        if found != expected {
+            return Err(LuhnError::BadCheckDigit {
+                position,
+                expected,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that `body`, which starts at byte `offset` of the input, for
+    /// error positions, is all in the alphabet.
+    fn check_body(&self, body: &str, offset: usize) -> Result<(), LuhnError> {
+        match body.char_indices().find(|&(_, c)| self.value(c).is_none()) {
+            Some((position, character)) => Err(LuhnError::InvalidCharacter {
+                position: offset + position,
+                character,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The value of `character`, if it's in the alphabet.
+    fn value(&self, character: char) -> Option<u32> {
+        self.alphabet
+            .iter()
+            .position(|&c| c == character)
+            .map(|value| value as u32)
+    }
+}
+
+impl Validator for Scheme {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.check(input).is_ok()
+    }
+}
+
+/// Why a [`SchemeSpec`] doesn't make sense.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecError(&'static str);
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid scheme: {}", self.0)
+    }
+}
+
+impl Error for SpecError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn luhn(check_position: CheckPosition) -> Scheme {
+        SchemeSpec {
+            name: "luhn".to_string(),
+            alphabet: "0123456789".to_string(),
+            check_alphabet: None,
+            weights: vec![2, 1],
+            modulus: 10,
+            sum_product_digits: true,
+            check_position,
+            min_len: 2,
+            max_len: None,
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn agrees_with_luhn() {
+        let scheme = luhn(CheckPosition::Last);
+        assert_eq!(scheme.checksum("7992739871"), Ok('3'));
+        for n in (0..10_000u64).step_by(7) {
+            let s = n.to_string();
+            assert_eq!(
+                scheme.validate(&s),
+                s.len() >= 2 && crate::valid(&s),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn puts_the_check_character_first() {
+        let scheme = luhn(CheckPosition::First);
+        assert_eq!(scheme.checksum("7992739871"), Ok('4'));
+        assert!(scheme.validate("47992739871"));
+        assert_eq!(
+            scheme.check("57992739871"),
+            Err(LuhnError::BadCheckDigit {
+                position: 0,
+                expected: '4',
+                found: '5'
+            })
+        );
+        assert_eq!(
+            scheme.check("4799273987a"),
+            Err(LuhnError::InvalidCharacter {
+                position: 10,
+                character: 'a'
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_nonsense_specs() {
+        let spec = luhn(CheckPosition::Last).spec().clone();
+        let bad = |change: fn(&mut SchemeSpec)| {
+            let mut spec = spec.clone();
+            change(&mut spec);
+            spec.build().is_err()
+        };
+        assert!(bad(|s| s.alphabet = "00".to_string()));
+        assert!(bad(|s| s.modulus = 11));
+        assert!(bad(|s| s.modulus = 1));
+        assert!(bad(|s| s.weights.clear()));
+        assert!(bad(|s| s.max_len = Some(1)));
+        assert!(!bad(|s| s.max_len = Some(2)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn loads_from_config() {
+        let json = r#"{ "name": "mod-7", "weights": [1, 3], "modulus": 7, "max_len": 8 }"#;
+        let spec: SchemeSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.alphabet, "0123456789");
+        assert_eq!(spec.check_position, CheckPosition::Last);
+        let scheme = spec.build().unwrap();
+        // 5 * 1 + 2 * 3 = 11, so the check digit is 3.
+        assert!(scheme.validate("253"));
+        assert!(!scheme.validate("123456789"));
+        assert!(serde_json::from_str::<SchemeSpec>(
+            r#"{ "name": "x", "weights": [1], "modulus": 7, "modulo": 7 }"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,51 @@
+//! sqlx column types for the newtypes.
+//!
+//! They're stored as text, and validated when decoded.  The round-trip
+//! tests are in `db-tests`, to keep SQLite out of the dev-dependencies.
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+use crate::{Imei, Isin, Pan};
+
+macro_rules! impl_sqlx {
+    ($($t:ty),*) => {$(
+        impl<DB: Database> Type<DB> for $t
+        where
+            String: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <String as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <String as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: Database> Encode<'q, DB> for $t
+        where
+            String: Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                <String as Encode<'q, DB>>::encode_by_ref(&self.0, buf)
+            }
+        }
+
+        impl<'r, DB: Database> Decode<'r, DB> for $t
+        where
+            String: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                let s = <String as Decode<'r, DB>>::decode(value)?;
+                Ok(<$t>::new(&s)?)
+            }
+        }
+    )*};
+}
+
+impl_sqlx!(Pan, Isin, Imei);
@@ -0,0 +1,142 @@
+//! Serial Shipping Container Codes, which identify pallets and parcels.
+//!
+//! An SSCC is 18 digits: an extension digit chosen by the company, a GS1
+//! company prefix, a serial reference, and a [GS1 check digit](crate::gs1).
+//!
+//! ```
+//! use luhn::sscc::{self, Sscc};
+//!
+//! assert!(sscc::valid("106141412345678908"));
+//! let sscc: Sscc = "106141412345678908".parse().unwrap();
+//! assert_eq!(sscc.extension_digit(), '1');
+//! assert_eq!(sscc.company_prefix(7), Some("0614141"));
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::{check_len, gs1, LuhnError};
+
+const LEN: usize = 18;
+
+/// The lengths a GS1 company prefix can have.
+const PREFIX_LENS: RangeInclusive<usize> = 4..=12;
+
+/// Computes the check digit for the first 17 digits of an SSCC.
+///
+/// Input must be 17 ASCII digits; panics otherwise.  Returns an ASCII
+/// digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    assert_eq!(body.len(), LEN - 1, "Not an SSCC body: {:?}", body);
+    gs1::checksum(body)
+}
+
+/// Whether `s` is 18 ASCII digits ending in a good check digit.
+pub fn valid(s: &str) -> bool {
+    check_len(s, LEN).and_then(|()| gs1::check(s)).is_ok()
+}
+
+/// A validated SSCC.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sscc(String);
+
+impl Sscc {
+    /// Validates `sscc`.
+    pub fn new(sscc: &str) -> Result<Sscc, LuhnError> {
+        check_len(sscc, LEN)?;
+        gs1::check(sscc)?;
+        Ok(Sscc(sscc.to_string()))
+    }
+
+    /// The SSCC as a string of digits.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The first digit, which the company uses to extend its range of
+    /// serial references.
+    pub fn extension_digit(&self) -> char {
+        self.0.as_bytes()[0] as char
+    }
+
+    /// The GS1 company prefix, given its length, which GS1 assigns along
+    /// with the prefix and isn't recorded in the SSCC.  `None` unless
+    /// `len` is between 4 and 12.
+    pub fn company_prefix(&self, len: usize) -> Option<&str> {
+        PREFIX_LENS.contains(&len).then(|| &self.0[1..1 + len])
+    }
+
+    /// The serial reference, which follows a company prefix of length
+    /// `prefix_len`.  `None` unless `prefix_len` is between 4 and 12.
+    pub fn serial_reference(&self, prefix_len: usize) -> Option<&str> {
+        PREFIX_LENS
+            .contains(&prefix_len)
+            .then(|| &self.0[1 + prefix_len..LEN - 1])
+    }
+}
+
+impl FromStr for Sscc {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Sscc, LuhnError> {
+        Sscc::new(s)
+    }
+}
+
+impl fmt::Display for Sscc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Sscc {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Sscc {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_ssccs() {
+        assert_eq!(checksum(b"00614141123456789"), b'0');
+        assert!(valid("006141411234567890"));
+        assert!(!valid("006141411234567891"));
+        assert_eq!(
+            Sscc::new("00614141123456789"),
+            Err(LuhnError::TooShort { len: 17, min: 18 })
+        );
+        assert_eq!(
+            Sscc::new("0061414112345678900"),
+            Err(LuhnError::TooLong { len: 19, max: 18 })
+        );
+    }
+
+    #[test]
+    fn splits_into_parts() {
+        let sscc = Sscc::new("106141412345678908").unwrap();
+        assert_eq!(sscc.extension_digit(), '1');
+        assert_eq!(sscc.company_prefix(7), Some("0614141"));
+        assert_eq!(sscc.serial_reference(7), Some("234567890"));
+        assert_eq!(sscc.company_prefix(12), Some("061414123456"));
+        assert_eq!(sscc.serial_reference(12), Some("7890"));
+        assert_eq!(sscc.company_prefix(3), None);
+        assert_eq!(sscc.serial_reference(13), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn checksum_needs_17_digits() {
+        checksum(b"1234");
+    }
+}
@@ -0,0 +1,246 @@
+//! Statistics about the identifiers seen over time.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Accumulates validation statistics.
+///
+/// Each thread can keep its own `Stats` and [`merge`](Self::merge) them
+/// when it's time to [`report`](Self::report).
+///
+/// So that memory stays bounded however varied the input, once there are
+/// more than twice [`Stats::MAX_PREFIXES`] failing prefixes only the most
+/// common `MAX_PREFIXES` are kept.  Counts for the rest are dropped, so
+/// after that the top of the report is approximate.
+///
+/// ```
+/// let mut stats = luhn::Stats::new();
+/// stats.record("4111111111111111");
+/// stats.record("4111111111111112");
+///
+/// let mut other = luhn::Stats::new();
+/// other.record("79927398713");
+/// stats.merge(other);
+///
+/// let report = stats.report();
+/// assert_eq!((report.valid, report.invalid), (2, 1));
+/// assert_eq!(report.lengths, [(11, 1), (16, 2)]);
+/// assert_eq!(report.top_failing_prefixes, [("411111".to_string(), 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    prefix_len: usize,
+    valid: u64,
+    invalid: u64,
+    lengths: BTreeMap<usize, u64>,
+    failing_prefixes: HashMap<String, u64>,
+}
+
+/// A snapshot of [`Stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsReport {
+    /// The number of valid identifiers recorded.
+    pub valid: u64,
+    /// The number of invalid identifiers recorded.
+    pub invalid: u64,
+    /// How many identifiers of each length, in characters, there were,
+    /// shortest first.
+    pub lengths: Vec<(usize, u64)>,
+    /// The prefixes invalid identifiers most often started with, with how
+    /// many did, most common first.  There are at most
+    /// [`StatsReport::TOP_PREFIXES`] of them.
+    pub top_failing_prefixes: Vec<(String, u64)>,
+}
+
+impl StatsReport {
+    /// The most prefixes `top_failing_prefixes` holds.
+    pub const TOP_PREFIXES: usize = 10;
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    /// How many failing prefixes are kept when there get to be too many.
+    pub const MAX_PREFIXES: usize = 1000;
+
+    /// Creates an empty accumulator, tracking six-character prefixes (the
+    /// length of a card's BIN).
+    pub fn new() -> Self {
+        Self::with_prefix_len(6)
+    }
+
+    /// Creates an empty accumulator, tracking prefixes of `prefix_len`
+    /// characters.
+    pub fn with_prefix_len(prefix_len: usize) -> Self {
+        Stats {
+            prefix_len,
+            valid: 0,
+            invalid: 0,
+            lengths: BTreeMap::new(),
+            failing_prefixes: HashMap::new(),
+        }
+    }
+
+    /// Validates `s` and records the result.  Returns whether it was
+    /// valid.
+    ///
+    /// `s` must be all ASCII digits, ending in a good check digit, to be
+    /// valid.
+    pub fn record(&mut self, s: &str) -> bool {
+        let valid = crate::check_digits(s).is_ok();
+        *self.lengths.entry(s.chars().count()).or_insert(0) += 1;
+        if valid {
+            self.valid += 1;
+        } else {
+            self.invalid += 1;
+            let end = s
+                .char_indices()
+                .nth(self.prefix_len)
+                .map_or(s.len(), |(i, _)| i);
+            *self
+                .failing_prefixes
+                .entry(s[..end].to_string())
+                .or_insert(0) += 1;
+            self.trim_prefixes();
+        }
+        valid
+    }
+
+    /// Adds everything recorded by `other`.
+    ///
+    /// Panics unless both track prefixes of the same length.
+    pub fn merge(&mut self, other: Stats) {
+        assert_eq!(
+            self.prefix_len, other.prefix_len,
+            "Merging stats with different prefix lengths"
+        );
+        self.valid += other.valid;
+        self.invalid += other.invalid;
+        for (len, count) in other.lengths {
+            *self.lengths.entry(len).or_insert(0) += count;
+        }
+        for (prefix, count) in other.failing_prefixes {
+            *self.failing_prefixes.entry(prefix).or_insert(0) += count;
+        }
+        self.trim_prefixes();
+    }
+
+    /// Summarizes everything recorded so far.
+    pub fn report(&self) -> StatsReport {
+        let mut prefixes = self.ranked_prefixes();
+        prefixes.truncate(StatsReport::TOP_PREFIXES);
+        StatsReport {
+            valid: self.valid,
+            invalid: self.invalid,
+            lengths: self.lengths.iter().map(|(&len, &n)| (len, n)).collect(),
+            top_failing_prefixes: prefixes,
+        }
+    }
+
+    /// The failing prefixes, most common first.
+    fn ranked_prefixes(&self) -> Vec<(String, u64)> {
+        let mut prefixes: Vec<_> = self
+            .failing_prefixes
+            .iter()
+            .map(|(prefix, &count)| (prefix.clone(), count))
+            .collect();
+        prefixes.sort_by(|(a, m), (b, n)| n.cmp(m).then_with(|| a.cmp(b)));
+        prefixes
+    }
+
+    /// Drops all but the most common [`MAX_PREFIXES`](Self::MAX_PREFIXES)
+    /// failing prefixes once there are twice that many, so that the
+    /// sorting isn't done on every record.
+    fn trim_prefixes(&mut self) {
+        if self.failing_prefixes.len() > 2 * Self::MAX_PREFIXES {
+            let mut prefixes = self.ranked_prefixes();
+            prefixes.truncate(Self::MAX_PREFIXES);
+            self.failing_prefixes = prefixes.into_iter().collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_failing_prefixes() {
+        let mut stats = Stats::with_prefix_len(2);
+        for s in ["41x", "41y", "55z", "3", "", "4111111111111111"] {
+            stats.record(s);
+        }
+        for i in 0..20 {
+            stats.record(&format!("{:02}x", i));
+        }
+        let report = stats.report();
+        assert_eq!(report.invalid, 25);
+        assert_eq!(report.top_failing_prefixes.len(), StatsReport::TOP_PREFIXES);
+        assert_eq!(
+            report.top_failing_prefixes[..3],
+            [
+                ("41".to_string(), 2),
+                ("".to_string(), 1),
+                ("00".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_a_bounded_number_of_prefixes() {
+        let mut stats = Stats::with_prefix_len(4);
+        for _ in 0..3 {
+            stats.record("411x");
+        }
+        for i in 0..10_000 {
+            stats.record(&format!("{:04}x", i));
+        }
+        assert!(stats.failing_prefixes.len() <= 2 * Stats::MAX_PREFIXES);
+        let report = stats.report();
+        assert_eq!(report.invalid, 10_003);
+        assert_eq!(report.top_failing_prefixes[0], ("411x".to_string(), 3));
+
+        let mut other = Stats::with_prefix_len(4);
+        for i in 0..2 * Stats::MAX_PREFIXES {
+            other.record(&format!("{:04}y", i));
+        }
+        stats.merge(other);
+        assert!(stats.failing_prefixes.len() <= 2 * Stats::MAX_PREFIXES);
+    }
+
+    #[test]
+    #[should_panic]
+    fn merging_different_prefix_lengths_panics() {
+        Stats::with_prefix_len(4).merge(Stats::new());
+    }
+
+    #[test]
+    fn merges_across_threads() {
+        let values = ["4111111111111111", "4111111111111112", "79927398713", "x"];
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    let mut stats = Stats::new();
+                    for value in values {
+                        stats.record(value);
+                    }
+                    stats
+                })
+            })
+            .collect();
+        let mut total = Stats::new();
+        for handle in handles {
+            total.merge(handle.join().unwrap());
+        }
+        let report = total.report();
+        assert_eq!((report.valid, report.invalid), (8, 8));
+        assert_eq!(report.lengths, [(1, 4), (11, 4), (16, 8)]);
+        assert_eq!(
+            report.top_failing_prefixes,
+            [("411111".to_string(), 4), ("x".to_string(), 4)]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! [proptest](https://docs.rs/proptest) strategies for valid and
+//! near-valid identifiers.
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+use crate::{checksum, Pan};
+
+/// Valid strings of `len` decimal digits.  Panics if `len` is zero.
+pub fn valid_luhn(len: usize) -> impl Strategy<Value = String> {
+    assert!(len > 0, "Can't generate a check digit for an empty string");
+    proptest::collection::vec(b'0'..=b'9', len - 1).prop_map(|mut digits| {
+        digits.push(checksum(&digits));
+        String::from_utf8(digits).unwrap()
+    })
+}
+
+/// Valid card numbers between 12 and 19 digits long.
+pub fn valid_pan() -> impl Strategy<Value = Pan> {
+    (12usize..=19)
+        .prop_flat_map(valid_luhn)
+        .prop_map(|s| Pan::new(&s).unwrap())
+}
+
+/// Card-number-like strings which are one digit away from validating.
+///
+/// The Luhn check catches every single-digit error, so these never
+/// validate.
+pub fn invalid_by_one_digit() -> impl Strategy<Value = String> {
+    (
+        (12usize..=19).prop_flat_map(valid_luhn),
+        any::<Index>(),
+        1u8..10,
+    )
+        .prop_map(|(s, index, delta)| {
+            let mut digits = s.into_bytes();
+            let i = index.index(digits.len());
+            let d = &mut digits[i];
+            *d = b'0' + (*d - b'0' + delta) % 10;
+            String::from_utf8(digits).unwrap()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valid;
+
+    proptest! {
+        #[test]
+        fn valid_luhn_validates(s in valid_luhn(16)) {
+            prop_assert_eq!(s.len(), 16);
+            prop_assert!(valid(&s));
+        }
+
+        #[test]
+        fn valid_pan_is_a_pan(pan in valid_pan()) {
+            prop_assert!(valid(pan.as_str()));
+        }
+
+        #[test]
+        fn invalid_by_one_digit_never_validates(s in invalid_by_one_digit()) {
+            prop_assert!(!valid(&s));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Turkish identity numbers (TC Kimlik No).
+//!
+//! A TCKN is eleven digits, the first never zero.  The tenth digit checks
+//! the first nine, weighting odd and even positions differently, and the
+//! eleventh is the sum of the first ten mod 10.
+//!
+//! ```
+//! assert!(luhn::tckn::valid("10000000146"));
+//! assert!(!luhn::tckn::valid("10000000147"));
+//! ```
+
+const LEN: usize = 11;
+
+/// Whether `s` is an 11-digit TCKN, not starting with zero, with good
+/// check digits.
+pub fn valid(s: &str) -> bool {
+    if s.len() != LEN || !s.bytes().all(|b| b.is_ascii_digit()) || s.starts_with('0') {
+        return false;
+    }
+    let mut d = [0; LEN];
+    for (d, b) in d.iter_mut().zip(s.bytes()) {
+        *d = i32::from(b - b'0');
+    }
+    let odd: i32 = d[..9].iter().step_by(2).sum();
+    let even: i32 = d[1..9].iter().step_by(2).sum();
+    let tenth = (odd * 7 - even).rem_euclid(10);
+    let eleventh = d[..10].iter().sum::<i32>() % 10;
+    d[9] == tenth && d[10] == eleventh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_tckns() {
+        assert!(valid("10000000146"));
+        assert!(valid("12345678950"));
+        assert!(!valid("12345678951"));
+        assert!(!valid("12345678940"));
+        assert!(!valid("1234567895"));
+        assert!(!valid("1234567895a"));
+    }
+
+    #[test]
+    fn rejects_leading_zero() {
+        // The check digits are right, but no TCKN starts with zero.
+        assert!(!valid("01234567840"));
+    }
+
+    #[test]
+    fn tenth_digit_wraps_when_negative() {
+        // 1 * 7 - (9 + 9 + 9 + 9) is -29, which wraps to 1.
+        assert!(valid("19090909018"));
+    }
+}
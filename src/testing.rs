@@ -0,0 +1,164 @@
+//! Wrapper types for property-based testing.
+
+use crate::checksum;
+
+/// A string of decimal digits which validates.
+///
+/// Implements `quickcheck::Arbitrary` and `arbitrary::Arbitrary` when the
+/// corresponding features are enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidLuhn(pub String);
+
+/// A string of decimal digits which is one digit off from validating.
+///
+/// Implements `quickcheck::Arbitrary` and `arbitrary::Arbitrary` when the
+/// corresponding features are enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidLuhn(pub String);
+
+impl ValidLuhn {
+    fn from_body(mut body: Vec<u8>) -> ValidLuhn {
+        body.push(checksum(&body));
+        ValidLuhn(String::from_utf8(body).unwrap())
+    }
+}
+
+impl InvalidLuhn {
+    /// Adds `delta` (mod 10) to the digit at `index`.  Luhn catches every
+    /// single-digit error, so this never validates.
+    fn from_valid(valid: ValidLuhn, index: usize, delta: u8) -> InvalidLuhn {
+        let mut digits = valid.0.into_bytes();
+        let i = index % digits.len();
+        digits[i] = b'0' + (digits[i] - b'0' + 1 + delta % 9) % 10;
+        InvalidLuhn(String::from_utf8(digits).unwrap())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls {
+    use quickcheck::{Arbitrary, Gen};
+
+    use super::*;
+
+    impl Arbitrary for ValidLuhn {
+        fn arbitrary(g: &mut Gen) -> ValidLuhn {
+            let len = 2 + usize::arbitrary(g) % g.size().max(1);
+            let body = (1..len)
+                .map(|_| *g.choose(b"0123456789").unwrap())
+                .collect();
+            ValidLuhn::from_body(body)
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = ValidLuhn>> {
+            // Drop leading digits of the body, keeping at least one.
+            let body = self.0.as_bytes()[..self.0.len() - 1].to_vec();
+            Box::new((1..body.len()).map(move |i| ValidLuhn::from_body(body[i..].to_vec())))
+        }
+    }
+
+    impl Arbitrary for InvalidLuhn {
+        fn arbitrary(g: &mut Gen) -> InvalidLuhn {
+            let valid = ValidLuhn::arbitrary(g);
+            InvalidLuhn::from_valid(valid, usize::arbitrary(g), u8::arbitrary(g))
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls {
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    use super::*;
+    use crate::{Isin, Pan};
+
+    fn digits(u: &mut Unstructured, len: usize) -> Result<Vec<u8>> {
+        (0..len)
+            .map(|_| Ok(b'0' + u.int_in_range(0..=9)?))
+            .collect()
+    }
+
+    impl<'a> Arbitrary<'a> for ValidLuhn {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<ValidLuhn> {
+            let len = u.int_in_range(1..=31)?;
+            Ok(ValidLuhn::from_body(digits(u, len)?))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for InvalidLuhn {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<InvalidLuhn> {
+            let valid = ValidLuhn::arbitrary(u)?;
+            Ok(InvalidLuhn::from_valid(
+                valid,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Pan {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Pan> {
+            let len = u.int_in_range(11..=18)?;
+            let ValidLuhn(pan) = ValidLuhn::from_body(digits(u, len)?);
+            Ok(Pan::new(&pan).unwrap())
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Isin {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Isin> {
+            const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+            let mut isin = Vec::with_capacity(12);
+            for _ in 0..2 {
+                isin.push(b'A' + u.int_in_range(0..=25)?);
+            }
+            for _ in 0..9 {
+                isin.push(*u.choose(ALPHANUMERIC)?);
+            }
+            isin.push(checksum(&isin));
+            Ok(Isin::new(std::str::from_utf8(&isin).unwrap()).unwrap())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valid;
+
+    #[test]
+    fn invalid_luhn_never_validates() {
+        let good = ValidLuhn::from_body(b"7992739871".to_vec());
+        assert!(valid(&good.0));
+        for index in 0..good.0.len() {
+            for delta in 0..=255 {
+                let bad = InvalidLuhn::from_valid(good.clone(), index, delta);
+                assert!(!valid(&bad.0), "{} validates", bad.0);
+            }
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_values_validate() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&bytes);
+        for _ in 0..50 {
+            assert!(valid(&ValidLuhn::arbitrary(&mut u).unwrap().0));
+            assert!(!valid(&InvalidLuhn::arbitrary(&mut u).unwrap().0));
+            crate::Pan::arbitrary(&mut u).unwrap();
+            crate::Isin::arbitrary(&mut u).unwrap();
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    quickcheck::quickcheck! {
+        fn valid_luhn_validates(s: ValidLuhn) -> bool {
+            valid(&s.0)
+        }
+
+        fn invalid_luhn_doesnt_validate(s: InvalidLuhn) -> bool {
+            !valid(&s.0)
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Parcel tracking numbers.
+//!
+//! Carriers each have their own check digit scheme; there's a module for
+//! each one.
+
+pub mod ups;
+pub mod usps_impb;
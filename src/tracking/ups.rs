@@ -0,0 +1,78 @@
+//! UPS "1Z" tracking numbers.
+//!
+//! A UPS tracking number is `1Z`, a six-character shipper number, a
+//! two-digit service code, an eight-digit package number and a check
+//! digit: 18 characters in all.  The check digit is a mod-10 scheme over
+//! the 15 characters between `1Z` and the check digit, with letters
+//! mapped onto digits.
+//!
+//! ```
+//! use luhn::tracking::ups;
+//!
+//! assert!(ups::valid("1Z999AA10123456784"));
+//! assert_eq!(ups::checksum(b"999AA1012345678"), b'4');
+//! ```
+
+const PREFIX: &str = "1Z";
+const LEN: usize = 18;
+
+/// Computes the check digit for the characters between `1Z` and the check
+/// digit.
+///
+/// Input must be uppercase alphanumeric ASCII; panics otherwise.  Returns
+/// an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    // Letters count as their position in the alphabet plus one, mod 10:
+    // A is 2, H is 9, I is 0, and so on.
+    fn encode_char(c: u8) -> u32 {
+        match c {
+            b'0'..=b'9' => u32::from(c - b'0'),
+            b'A'..=b'Z' => u32::from(c - b'A' + 2) % 10,
+            _ => panic!("Not alphanumeric: {}", c),
+        }
+    }
+
+    // Every second character, counting from the first, is doubled.
+    let sum: u32 = body
+        .iter()
+        .zip([1, 2].iter().cycle())
+        .map(|(&c, &weight)| encode_char(c) * weight)
+        .sum();
+    (10 - sum % 10) as u8 % 10 + b'0'
+}
+
+/// Whether `s` is a UPS tracking number with a good check digit.
+/// Letters must be uppercase.
+pub fn valid(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    s.len() == LEN
+        && s.starts_with(PREFIX)
+        && bytes[2..]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        && bytes[LEN - 1] == checksum(&bytes[2..LEN - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_tracking_numbers() {
+        assert!(valid("1Z999AA10123456784"));
+        assert!(valid("1Z12345E6605272234"));
+        assert!(!valid("1Z12345E6605272235"));
+        assert!(!valid("1Z12345E660527223"));
+        assert!(!valid("2Z12345E6605272234"));
+        assert!(!valid("1z12345e6605272234"));
+        assert!(!valid("1Z12345E66052722é4"));
+    }
+
+    #[test]
+    fn maps_letters_onto_digits() {
+        // I and S wrap round to 0, like the digit they replace.
+        assert_eq!(checksum(b"I"), checksum(b"0"));
+        assert_eq!(checksum(b"S"), checksum(b"0"));
+        assert_eq!(checksum(b"Z"), checksum(b"7"));
+    }
+}
@@ -0,0 +1,44 @@
+//! USPS Intelligent Mail package barcode (IMpb) tracking numbers.
+//!
+//! The check digit uses the same 3-1 weighting as [GS1](crate::gs1).
+//! Tracking numbers are 20, 22 or 26 digits; strip the `420` and ZIP
+//! code routing prefix from a scanned barcode before validating.
+//!
+//! ```
+//! use luhn::tracking::usps_impb;
+//!
+//! assert!(usps_impb::valid("9205590164917312751089"));
+//! assert_eq!(usps_impb::checksum(b"920559016491731275108"), b'9');
+//! ```
+
+use crate::gs1;
+
+const LENS: [usize; 3] = [20, 22, 26];
+
+/// Computes the check digit for all but the last digit of a tracking
+/// number.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    gs1::checksum(body)
+}
+
+/// Whether `s` is a 20, 22 or 26-digit tracking number with a good check
+/// digit.
+pub fn valid(s: &str) -> bool {
+    LENS.contains(&s.len()) && gs1::valid(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_tracking_numbers() {
+        assert!(valid("9205590164917312751089"));
+        assert!(!valid("9205590164917312751088"));
+        assert!(valid("94001118992233976235"));
+        assert!(!valid("920559016491731275108"));
+        assert!(!valid("9205 5901 6491 7312 7510 89"));
+    }
+}
@@ -0,0 +1,187 @@
+//! UIC numbers, which identify European railway vehicles.
+//!
+//! A UIC number is 12 digits, the last a Luhn check digit, usually
+//! written grouped with a hyphen before the check digit, as in
+//! `"91 80 6101 004-0"`.
+//!
+//! ```
+//! use luhn::uic::{self, Uic};
+//!
+//! assert!(uic::valid("91 80 6101 004-0"));
+//! let uic: Uic = "918061010040".parse().unwrap();
+//! assert_eq!(uic.country_code(), "80");
+//! assert_eq!(uic.to_string(), "91 80 6101 004-0");
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::small::SmallString;
+use crate::{check_digits, check_len, LuhnError};
+
+const LEN: usize = 12;
+
+/// Whether `s` is a UIC number with a good check digit, either as bare
+/// digits or grouped with spaces and a hyphen before the check digit.
+pub fn valid(s: &str) -> bool {
+    digits(s).is_ok()
+}
+
+/// The bare digits of a valid `uic`, on the stack.
+fn digits(uic: &str) -> Result<SmallString, LuhnError> {
+    let mut digits = SmallString::new();
+    let mut hyphen = false;
+    for (position, character) in uic.char_indices() {
+        match character {
+            '0'..='9' => digits.push(character),
+            ' ' => {}
+            '-' if !hyphen && digits.len() == LEN - 1 => hyphen = true,
+            _ => {
+                return Err(LuhnError::InvalidCharacter {
+                    position,
+                    character,
+                })
+            }
+        }
+    }
+    check_len(&digits, LEN)?;
+    check_digits(&digits)?;
+    Ok(digits)
+}
+
+/// A validated UIC vehicle number.
+///
+/// Displays in the usual grouped form; [`as_str`](Uic::as_str) has the
+/// bare digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Uic(String);
+
+impl Uic {
+    /// Validates `uic`, ignoring spaces and a hyphen before the check
+    /// digit.  Length and check digit errors refer to the bare digits.
+    pub fn new(uic: &str) -> Result<Uic, LuhnError> {
+        digits(uic).map(|digits| Uic(digits.to_string()))
+    }
+
+    /// The number as 12 bare digits.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The first two digits, which give the vehicle's type and
+    /// interoperability, eg. `"91"` for a locomotive.
+    pub fn type_code(&self) -> &str {
+        &self.0[..2]
+    }
+
+    /// The registering country, eg. `"80"` for Germany.
+    pub fn country_code(&self) -> &str {
+        &self.0[2..4]
+    }
+
+    /// The four digits describing the vehicle's technical details, such
+    /// as its class.
+    pub fn series(&self) -> &str {
+        &self.0[4..8]
+    }
+
+    /// The serial number within the series.
+    pub fn serial(&self) -> &str {
+        &self.0[8..11]
+    }
+
+    /// The Luhn check digit.
+    pub fn check_digit(&self) -> char {
+        self.0.as_bytes()[LEN - 1] as char
+    }
+}
+
+impl FromStr for Uic {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Uic, LuhnError> {
+        Uic::new(s)
+    }
+}
+
+impl fmt::Display for Uic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}-{}",
+            self.type_code(),
+            self.country_code(),
+            self.series(),
+            self.serial(),
+            self.check_digit()
+        )
+    }
+}
+
+impl AsRef<str> for Uic {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Uic {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_formatted_numbers() {
+        let uic = Uic::new("91 80 6101 004-0").unwrap();
+        assert_eq!(uic.as_str(), "918061010040");
+        assert_eq!(uic.type_code(), "91");
+        assert_eq!(uic.country_code(), "80");
+        assert_eq!(uic.series(), "6101");
+        assert_eq!(uic.serial(), "004");
+        assert_eq!(uic.check_digit(), '0');
+        assert_eq!(Uic::new("918061010040"), Ok(uic.clone()));
+        assert_eq!(uic.to_string().parse(), Ok(uic));
+    }
+
+    #[test]
+    fn rejects_bad_numbers() {
+        assert_eq!(
+            Uic::new("91-80 6101 004 4"),
+            Err(LuhnError::InvalidCharacter {
+                position: 2,
+                character: '-'
+            })
+        );
+        assert_eq!(
+            Uic::new("91 80 6101 004--4"),
+            Err(LuhnError::InvalidCharacter {
+                position: 15,
+                character: '-'
+            })
+        );
+        assert_eq!(
+            Uic::new("91 80 6101 04-4"),
+            Err(LuhnError::InvalidCharacter {
+                position: 13,
+                character: '-'
+            })
+        );
+        assert_eq!(
+            Uic::new("91 80 6101 04"),
+            Err(LuhnError::TooShort { len: 10, min: 12 })
+        );
+        assert_eq!(
+            Uic::new("91 80 6101 004-5"),
+            Err(LuhnError::BadCheckDigit {
+                position: 11,
+                expected: '0',
+                found: '5'
+            })
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Custom validation functions for the [validator](https://docs.rs/validator)
+//! crate.
+//!
+//! ```
+//! use validator::Validate;
+//!
+//! #[derive(Validate)]
+//! struct Payment {
+//!     #[validate(custom(function = "luhn::validator::validate_pan"))]
+//!     card_number: String,
+//! }
+//!
+//! let payment = Payment {
+//!     card_number: "4111111111111112".to_string(),
+//! };
+//! let errors = payment.validate().unwrap_err();
+//! assert_eq!(errors.field_errors()["card_number"][0].code, "luhn");
+//! ```
+//!
+//! Failures have the code `"luhn"` and a message describing what's wrong.
+
+use std::borrow::Cow;
+
+use ::validator::ValidationError;
+
+use crate::{check_digits, Imei, Isin, LuhnError, Pan};
+
+fn to_validation_error(err: LuhnError) -> ValidationError {
+    ValidationError::new("luhn").with_message(Cow::Owned(err.to_string()))
+}
+
+/// Checks that `value` is a string of decimal digits which validates.
+pub fn validate_luhn(value: &str) -> Result<(), ValidationError> {
+    check_digits(value).map_err(to_validation_error)
+}
+
+/// Checks that `value` is a valid card number of 12 to 19 digits.
+pub fn validate_pan(value: &str) -> Result<(), ValidationError> {
+    Pan::new(value).map(drop).map_err(to_validation_error)
+}
+
+/// Checks that `value` is a valid 15-digit IMEI.
+pub fn validate_imei(value: &str) -> Result<(), ValidationError> {
+    Imei::new(value).map(drop).map_err(to_validation_error)
+}
+
+/// Checks that `value` is a valid ISIN.
+pub fn validate_isin(value: &str) -> Result<(), ValidationError> {
+    Isin::new(value).map(drop).map_err(to_validation_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_failures() {
+        assert!(validate_luhn("18").is_ok());
+        assert!(validate_pan("18").is_err());
+        assert!(validate_pan("4111111111111111").is_ok());
+        assert!(validate_imei("490154203237518").is_ok());
+        assert!(validate_isin("US0378331005").is_ok());
+
+        let err = validate_luhn("4111111111111112").unwrap_err();
+        assert_eq!(err.code, "luhn");
+        assert_eq!(
+            err.message.unwrap(),
+            "bad check digit '2' at position 15, expected '1'"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Validation with explicit length rules.
+
+use crate::LuhnError;
+
+/// The outcome of [`validity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Validity {
+    /// At least two digits, ending in a good check digit.
+    Valid,
+    /// Something other than ASCII digits, or a bad check digit.
+    Invalid,
+    /// All digits, but fewer than two of them: too short to have both a
+    /// payload and a check digit.
+    TooShort,
+}
+
+impl Validity {
+    /// Whether this is [`Validity::Valid`].  Too-short input isn't valid.
+    pub fn is_valid(self) -> bool {
+        self == Validity::Valid
+    }
+}
+
+/// The fewest digits [`validity`] accepts: one digit of payload and the
+/// check digit.
+const MIN_LEN: usize = 2;
+
+/// Validates `s`, distinguishing input too short to mean anything.
+///
+/// [`valid`](crate::valid) says `""` and `"0"` are valid, since there's
+/// no digit to fail the check, and `"5"` isn't.  Systems disagree about
+/// what those should be, so this returns [`Validity::TooShort`] for all
+/// of them, leaving the decision to the caller:
+///
+/// ```
+/// use luhn::{validity, Validity};
+///
+/// assert_eq!(validity(""), Validity::TooShort);
+/// assert_eq!(validity("5"), Validity::TooShort);
+/// assert_eq!(validity("18"), Validity::Valid);
+/// assert_eq!(validity("19"), Validity::Invalid);
+/// assert_eq!(validity("1-8"), Validity::Invalid);
+///
+/// // Treating short input as acceptable instead:
+/// let lenient = |s| matches!(validity(s), Validity::Valid | Validity::TooShort);
+/// assert!(lenient("5"));
+/// ```
+pub fn validity(s: &str) -> Validity {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        Validity::Invalid
+    } else if s.len() < MIN_LEN {
+        Validity::TooShort
+    } else if crate::check_digits(s).is_ok() {
+        Validity::Valid
+    } else {
+        Validity::Invalid
+    }
+}
+
+/// Length limits for [`validate_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidateConfig {
+    /// The fewest characters allowed.
+    pub min_len: usize,
+    /// The most characters allowed, if there's a limit.
+    pub max_len: Option<usize>,
+}
+
+impl ValidateConfig {
+    /// Payment card numbers: at least 12 digits, and at most the 19 allowed
+    /// by ISO/IEC 7812, so that oversized concatenated fields don't
+    /// validate by accident.
+    ///
+    /// ```
+    /// use luhn::{validate_with, LuhnError, ValidateConfig};
+    ///
+    /// let two_cards = "41111111111111114111111111111111";
+    /// assert_eq!(
+    ///     validate_with(two_cards, &ValidateConfig::PAN),
+    ///     Err(LuhnError::TooLong { len: 32, max: 19 })
+    /// );
+    /// ```
+    pub const PAN: ValidateConfig = ValidateConfig {
+        min_len: 12,
+        max_len: Some(19),
+    };
+}
+
+impl Default for ValidateConfig {
+    /// At least two characters, like [`validity`], and no maximum.
+    fn default() -> Self {
+        ValidateConfig {
+            min_len: MIN_LEN,
+            max_len: None,
+        }
+    }
+}
+
+/// Validates `s`, which must be all ASCII digits within the length limits
+/// in `config` and end in a good check digit.
+///
+/// A short string whose check digit happens to match is almost always a
+/// data bug rather than a real identifier, so set `min_len` to the
+/// shortest identifier you expect:
+///
+/// ```
+/// use luhn::{validate_with, LuhnError, ValidateConfig};
+///
+/// let config = ValidateConfig {
+///     min_len: 8,
+///     ..ValidateConfig::default()
+/// };
+/// assert_eq!(validate_with("18", &config), Err(LuhnError::TooShort { len: 2, min: 8 }));
+/// assert_eq!(validate_with("79927398713", &config), Ok(()));
+/// ```
+pub fn validate_with(s: &str, config: &ValidateConfig) -> Result<(), LuhnError> {
+    // Characters rather than bytes, as in `Pattern::extract`, for errors
+    // about non-ASCII input.
+    let len = s.chars().count();
+    if len < config.min_len {
+        return Err(LuhnError::TooShort {
+            len,
+            min: config.min_len,
+        });
+    }
+    if let Some(max) = config.max_len {
+        if len > max {
+            return Err(LuhnError::TooLong { len, max });
+        }
+    }
+    crate::check_digits(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_limits() {
+        let config = ValidateConfig {
+            min_len: 3,
+            max_len: Some(5),
+        };
+        assert_eq!(
+            validate_with("18", &config),
+            Err(LuhnError::TooShort { len: 2, min: 3 })
+        );
+        assert_eq!(validate_with("125", &config), Ok(()));
+        assert_eq!(validate_with("12344", &config), Ok(()));
+        assert_eq!(
+            validate_with("123455", &config),
+            Err(LuhnError::TooLong { len: 6, max: 5 })
+        );
+        assert!(matches!(
+            validate_with("12a", &config),
+            Err(LuhnError::InvalidCharacter { position: 2, .. })
+        ));
+        assert!(matches!(
+            validate_with("1éé", &config),
+            Err(LuhnError::InvalidCharacter { position: 1, .. })
+        ));
+        assert_eq!(
+            validate_with("éé", &config),
+            Err(LuhnError::TooShort { len: 2, min: 3 })
+        );
+    }
+
+    #[test]
+    fn pan_mode() {
+        let pan = &ValidateConfig::PAN;
+        assert_eq!(validate_with("4111111111111111", pan), Ok(()));
+        assert_eq!(validate_with("4222222222222", pan), Ok(()));
+        assert_eq!(
+            validate_with("79927398713", pan),
+            Err(LuhnError::TooShort { len: 11, min: 12 })
+        );
+        assert_eq!(validate_with(&"0".repeat(19), pan), Ok(()));
+        assert_eq!(
+            validate_with(&"0".repeat(20), pan),
+            Err(LuhnError::TooLong { len: 20, max: 19 })
+        );
+    }
+
+    #[test]
+    fn defaults_agree_with_validity() {
+        for s in ["", "0", "18", "19", "1a", "4111111111111111"] {
+            assert_eq!(
+                validate_with(s, &ValidateConfig::default()).is_ok(),
+                validity(s).is_valid(),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn short_input() {
+        for s in ["", "0", "5", "9"] {
+            assert_eq!(validity(s), Validity::TooShort, "{:?}", s);
+            assert!(!validity(s).is_valid());
+        }
+        assert_eq!(validity("x"), Validity::Invalid);
+    }
+
+    #[test]
+    fn agrees_with_valid_beyond_one_digit() {
+        for n in 10..1000 {
+            let s = n.to_string();
+            assert_eq!(validity(&s).is_valid(), crate::valid(&s), "{}", s);
+        }
+        assert!(validity("4111111111111111").is_valid());
+        assert_eq!(validity("4111 1111 1111 1111"), Validity::Invalid);
+    }
+}
@@ -0,0 +1,88 @@
+//! The Verhoeff check digit, which catches all single-digit errors and
+//! all transpositions of adjacent digits, unlike Luhn.
+//!
+//! It's used by India's Aadhaar numbers, among others.
+//!
+//! ```
+//! use luhn::verhoeff;
+//!
+//! assert_eq!(verhoeff::checksum(b"236"), b'3');
+//! assert!(verhoeff::valid("2363"));
+//! ```
+
+use crate::check_digits_with;
+
+/// Multiplication in the dihedral group D5.
+const MULTIPLY: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+/// The permutation applied to a digit, by its position from the right,
+/// mod 8.
+const PERMUTE: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+const INVERSE: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// Computes the Verhoeff check digit for `body`.
+///
+/// Input must be ASCII digits; panics otherwise.  Returns an ASCII digit.
+pub fn checksum(body: &[u8]) -> u8 {
+    let check = body.iter().rev().enumerate().fold(0, |check, (i, &b)| {
+        assert!(b.is_ascii_digit(), "Not a digit: {}", b);
+        // The check digit will take position 0, so the body starts at 1.
+        MULTIPLY[usize::from(check)][usize::from(PERMUTE[(i + 1) % 8][usize::from(b - b'0')])]
+    });
+    INVERSE[usize::from(check)] + b'0'
+}
+
+/// Whether `s` is ASCII digits ending in a good Verhoeff check digit.
+pub fn valid(s: &str) -> bool {
+    check_digits_with(s, checksum).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_check_digits() {
+        assert_eq!(checksum(b"236"), b'3');
+        assert_eq!(checksum(b"12345"), b'1');
+        assert_eq!(checksum(b"142857"), b'0');
+        assert_eq!(checksum(b""), b'0');
+        assert!(valid("123451"));
+        assert!(!valid("123452"));
+        assert!(!valid(""));
+        assert!(!valid("12345a"));
+    }
+
+    #[test]
+    fn catches_adjacent_transpositions() {
+        let good = b"123451";
+        for i in 0..good.len() - 1 {
+            if good[i] != good[i + 1] {
+                let mut bad = *good;
+                bad.swap(i, i + 1);
+                assert!(!valid(std::str::from_utf8(&bad).unwrap()));
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Vehicle identification numbers (VINs), as defined by ISO 3779.
+//!
+//! A VIN is 17 uppercase letters and digits, never `I`, `O` or `Q`.
+//! North American VINs have a check digit in the ninth position: each
+//! character is transliterated to a number, weighted by its position,
+//! and the sum taken mod 11, with 10 written as `X`.  Elsewhere the
+//! ninth character is often not a check digit, so only use this for
+//! VINs which are meant to have one.
+//!
+//! ```
+//! use luhn::vin;
+//!
+//! assert!(vin::valid("1M8GDM9AXKP042788"));
+//! assert_eq!(vin::check_digit(b"1M8GDM9A_KP042788"), b'X');
+//! ```
+
+const LEN: usize = 17;
+const CHECK_POSITION: usize = 8;
+const WEIGHTS: [u32; LEN] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// Computes the check digit of a VIN.
+///
+/// Input must be 17 characters, all uppercase letters other than `I`, `O`
+/// and `Q` or ASCII digits, except for the ninth, which is ignored;
+/// panics otherwise.  Returns an ASCII digit or `X`.
+pub fn check_digit(vin: &[u8]) -> u8 {
+    assert_eq!(vin.len(), LEN, "Not a VIN: {:?}", vin);
+    let sum: u32 = vin
+        .iter()
+        .zip(WEIGHTS.iter())
+        .enumerate()
+        .filter(|&(i, _)| i != CHECK_POSITION)
+        .map(|(_, (&c, &weight))| {
+            transliterate(c).unwrap_or_else(|| panic!("Not allowed in a VIN: {}", c)) * weight
+        })
+        .sum();
+    match sum % 11 {
+        10 => b'X',
+        d => d as u8 + b'0',
+    }
+}
+
+/// Whether `s` is a VIN with a good check digit.
+pub fn valid(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == LEN
+        && bytes.iter().all(|&c| transliterate(c).is_some())
+        && check_digit(bytes) == bytes[CHECK_POSITION]
+}
+
+/// The number a VIN character counts as, if it's allowed.
+fn transliterate(c: u8) -> Option<u32> {
+    let value = match c {
+        b'0'..=b'9' => c - b'0',
+        b'A'..=b'H' => c - b'A' + 1,
+        b'J'..=b'N' => c - b'J' + 1,
+        b'P' => 7,
+        b'R' => 9,
+        b'S'..=b'Z' => c - b'S' + 2,
+        _ => return None,
+    };
+    Some(u32::from(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_vins() {
+        assert!(valid("1M8GDM9AXKP042788"));
+        assert!(valid("11111111111111111"));
+        assert!(valid("1HGCM82633A004352"));
+        assert!(!valid("1M8GDM9A1KP042788"));
+        assert!(!valid("1M8GDM9AXKP04278"));
+        assert!(!valid("1m8gdm9axkp042788"));
+        // I, O and Q look too much like 1 and 0.
+        assert!(!valid("1M8GDM9AXKP0427O8"));
+    }
+
+    #[test]
+    fn transliterates_letters() {
+        let values: Vec<u32> = (b'A'..=b'Z').filter_map(transliterate).collect();
+        assert_eq!(
+            values,
+            [1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 7, 9, 2, 3, 4, 5, 6, 7, 8, 9]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_digit_rejects_bad_characters() {
+        check_digit(b"1M8GDM9AXKP0427O8");
+    }
+}
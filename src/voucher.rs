@@ -0,0 +1,298 @@
+//! Voucher, coupon and gift-card codes.
+//!
+//! A [`VoucherFormat`] lays codes out as its layout says: each `X` is a
+//! character from [`Alphabet::VOUCHER`], which has no `0`, `O`, `1` or `I`
+//! to mix up, `C` is the Luhn mod 32 check character, and anything else
+//! separates groups.  Codes are checked ignoring case, whitespace and the
+//! layout's separators, the way people read them out and type them in.
+//!
+//! ```
+//! use luhn::voucher::VoucherFormat;
+//!
+//! let format = VoucherFormat::new("XXXX-XXXX-XXXC").unwrap();
+//! assert!(format.valid("HAPP-YBDA-Y27H"));
+//! assert_eq!(format.normalize("happ ybda y27h").as_deref(), Some("HAPP-YBDA-Y27H"));
+//! assert!(!format.valid("HAPP-YBDA-Y27J"));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "rand")]
+use rand::{Rng, RngCore};
+
+use crate::mod_n::Alphabet;
+use crate::small::SmallString;
+use crate::Validator;
+
+/// A layout for voucher codes; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoucherFormat {
+    layout: String,
+    /// How many `X`s and `C`s there are.
+    len: usize,
+    /// Where the `C` is among them.
+    check: usize,
+}
+
+impl VoucherFormat {
+    /// Compiles `layout`, which needs exactly one `C`.  Separators can't be
+    /// letters or digits, which would be confused with the code.
+    pub fn new(layout: &str) -> Result<VoucherFormat, LayoutError> {
+        let mut len = 0;
+        let mut check = None;
+        for (position, character) in layout.char_indices() {
+            match character {
+                'X' => len += 1,
+                'C' if check.is_some() => {
+                    return Err(LayoutError::SecondCheckCharacter { position })
+                }
+                'C' => {
+                    check = Some(len);
+                    len += 1;
+                }
+                c if c.is_alphanumeric() => {
+                    return Err(LayoutError::InvalidSeparator {
+                        position,
+                        character,
+                    })
+                }
+                _ => {}
+            }
+        }
+        let check = check.ok_or(LayoutError::NoCheckCharacter)?;
+        Ok(VoucherFormat {
+            layout: layout.to_string(),
+            len,
+            check,
+        })
+    }
+
+    /// The layout as written.
+    pub fn as_str(&self) -> &str {
+        &self.layout
+    }
+
+    /// Whether `code` is a voucher in this format with a good check
+    /// character.
+    pub fn valid(&self, code: &str) -> bool {
+        self.symbols(code).is_some()
+    }
+
+    /// Validates `code` as [`valid`](VoucherFormat::valid) does, returning
+    /// it laid out properly, in uppercase.
+    pub fn normalize(&self, code: &str) -> Option<String> {
+        self.symbols(code).map(|symbols| self.lay_out(&symbols))
+    }
+
+    /// The code's characters, in uppercase and without separators, if
+    /// it's valid.
+    fn symbols(&self, code: &str) -> Option<SmallString> {
+        let mut symbols = SmallString::new();
+        for c in code.chars() {
+            if c.is_whitespace() || self.is_separator(c) {
+                continue;
+            }
+            let c = c.to_ascii_uppercase();
+            if !Alphabet::VOUCHER.as_str().contains(c) {
+                return None;
+            }
+            symbols.push(c);
+        }
+        if symbols.len() != self.len || !Alphabet::VOUCHER.valid(&symbols) {
+            return None;
+        }
+        Some(symbols)
+    }
+
+    /// Generates a random code, each `X` chosen uniformly at random.
+    #[cfg(feature = "rand")]
+    pub fn generate<R: RngCore + ?Sized>(&self, rng: &mut R) -> String {
+        let alphabet = Alphabet::VOUCHER.as_str().as_bytes();
+        let mut symbols: Vec<u8> = (0..self.len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+            .collect();
+        // As with digits, exactly one character makes the code validate.
+        for &c in alphabet {
+            symbols[self.check] = c;
+            if Alphabet::VOUCHER.valid(std::str::from_utf8(&symbols).unwrap()) {
+                break;
+            }
+        }
+        self.lay_out(std::str::from_utf8(&symbols).unwrap())
+    }
+
+    /// Generates `count` different random codes, for issuing a batch.
+    ///
+    /// Panics if the format doesn't have `count` different codes.  Codes
+    /// are drawn until there are enough, so asking for nearly all of them
+    /// is slow; leave plenty of room so that they're hard to guess anyway.
+    #[cfg(feature = "rand")]
+    pub fn generate_unique<R: RngCore + ?Sized>(&self, count: usize, rng: &mut R) -> Vec<String> {
+        let possible = (Alphabet::VOUCHER.len() as u64).checked_pow(self.len as u32 - 1);
+        assert!(
+            possible.map_or(true, |possible| count as u64 <= possible),
+            "{:?} doesn't have {} different codes",
+            self.layout,
+            count
+        );
+        let mut seen = std::collections::HashSet::with_capacity(count);
+        let mut codes = Vec::with_capacity(count);
+        while codes.len() < count {
+            let code = self.generate(rng);
+            if seen.insert(code.clone()) {
+                codes.push(code);
+            }
+        }
+        codes
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        c != 'X' && c != 'C' && self.layout.contains(c)
+    }
+
+    /// Puts `symbols` in the layout's `X`s and `C`.
+    fn lay_out(&self, symbols: &str) -> String {
+        let mut symbols = symbols.chars();
+        self.layout
+            .chars()
+            .map(|c| match c {
+                'X' | 'C' => symbols.next().unwrap(),
+                c => c,
+            })
+            .collect()
+    }
+}
+
+impl Validator for VoucherFormat {
+    fn name(&self) -> &str {
+        &self.layout
+    }
+
+    fn validate(&self, input: &str) -> bool {
+        self.valid(input)
+    }
+}
+
+/// Why a [`VoucherFormat`] couldn't be compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// There's no `C`.
+    NoCheckCharacter,
+    /// There's another `C` at byte offset `position`.
+    SecondCheckCharacter { position: usize },
+    /// There's a letter or digit other than `X` and `C` at byte offset
+    /// `position`.
+    InvalidSeparator { position: usize, character: char },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::NoCheckCharacter => f.write_str("layout has no check character 'C'"),
+            LayoutError::SecondCheckCharacter { position } => write!(
+                f,
+                "layout has a second check character at position {}",
+                position
+            ),
+            LayoutError::InvalidSeparator {
+                position,
+                character,
+            } => write!(
+                f,
+                "layout has a letter or digit {:?} at position {}",
+                character, position
+            ),
+        }
+    }
+}
+
+impl Error for LayoutError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_typed_codes() {
+        let format = VoucherFormat::new("XXXX-XXXX-XXXC").unwrap();
+        for typed in &[
+            "SPRN-G5SA-LE92",
+            "sprn-g5sa-le92",
+            "SPRNG5SALE92",
+            " sprn g5sa le92 ",
+        ] {
+            assert_eq!(
+                format.normalize(typed).as_deref(),
+                Some("SPRN-G5SA-LE92"),
+                "{}",
+                typed
+            );
+        }
+        assert!(!format.valid("SPRN-G5SA-LE93"));
+        assert!(!format.valid("SPRN-G5SA-LE9"));
+        assert!(!format.valid("SPRN-G5SA-LE922"));
+        assert!(!format.valid("SPRN/G5SA/LE92"));
+        // Not in the alphabet, even where the check would work out.
+        assert!(!format.valid("SPRN-G5SA-0E92"));
+    }
+
+    #[test]
+    fn check_character_can_go_anywhere() {
+        let format = VoucherFormat::new("C.XXXXXXXXXXX").unwrap();
+        assert!(format.valid("T.HAPPYBDAY27"));
+        assert!(!format.valid("H.HAPPYBDAY27"));
+    }
+
+    #[test]
+    fn rejects_bad_layouts() {
+        assert_eq!(
+            VoucherFormat::new("XXXX-XXXX"),
+            Err(LayoutError::NoCheckCharacter)
+        );
+        assert_eq!(
+            VoucherFormat::new("XXC-XXC"),
+            Err(LayoutError::SecondCheckCharacter { position: 6 })
+        );
+        assert_eq!(
+            VoucherFormat::new("GIFT-XXXC"),
+            Err(LayoutError::InvalidSeparator {
+                position: 0,
+                character: 'G'
+            })
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generates_valid_codes() {
+        let mut rng = rand::thread_rng();
+        for layout in &["XXXX-XXXX-XXXC", "C.XXXXXXXXXXX", "XX C XX"] {
+            let format = VoucherFormat::new(layout).unwrap();
+            for _ in 0..50 {
+                let code = format.generate(&mut rng);
+                assert_eq!(format.normalize(&code), Some(code.clone()), "{}", layout);
+            }
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn batches_are_unique() {
+        let format = VoucherFormat::new("XXC").unwrap();
+        let mut codes = format.generate_unique(1024, &mut rand::thread_rng());
+        assert!(codes.iter().all(|code| format.valid(code)));
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), 1024);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn batches_must_fit() {
+        let format = VoucherFormat::new("XXC").unwrap();
+        format.generate_unique(1025, &mut rand::thread_rng());
+    }
+}
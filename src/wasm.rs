@@ -0,0 +1,113 @@
+//! JavaScript bindings, via wasm-bindgen.
+//!
+//! Build the module, then generate an npm package from it with
+//! [`wasm-bindgen`](https://rustwasm.github.io/wasm-bindgen/):
+//!
+//! ```sh
+//! cargo rustc --release --lib --target wasm32-unknown-unknown --crate-type cdylib --features wasm
+//! wasm-bindgen --target bundler --out-dir pkg target/wasm32-unknown-unknown/release/luhn.wasm
+//! ```
+//!
+//! The generated `pkg/` directory can be published as-is.
+
+use wasm_bindgen::prelude::*;
+
+/// Validates the given string of ASCII digits using the Luhn algorithm.
+/// Empty input, or anything other than digits, is invalid.
+#[wasm_bindgen(js_name = valid)]
+pub fn valid(s: &str) -> bool {
+    crate::check_digits(s).is_ok()
+}
+
+/// Computes the check digit for the given string, returning it as a
+/// one-character string.  Throws unless the input is uppercase
+/// alphanumeric ASCII.
+#[wasm_bindgen(js_name = checksum)]
+pub fn checksum(s: &str) -> Result<String, JsError> {
+    Ok((crate::try_checksum(s.as_bytes())? as char).to_string())
+}
+
+/// Appends the check digit to the given string.  Throws unless the input
+/// is uppercase alphanumeric ASCII.
+#[wasm_bindgen(js_name = withChecksum)]
+pub fn with_checksum(s: &str) -> Result<String, JsError> {
+    Ok(format!("{}{}", s, checksum(s)?))
+}
+
+/// A card number found by `findCandidates`.  `start` and `end` are
+/// JavaScript string indices (UTF-16 code units).
+#[wasm_bindgen]
+pub struct Candidate {
+    start: usize,
+    end: usize,
+    digits: String,
+}
+
+#[wasm_bindgen]
+impl Candidate {
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The number's digits, separators removed.
+    #[wasm_bindgen(getter)]
+    pub fn digits(&self) -> String {
+        self.digits.clone()
+    }
+}
+
+/// Finds card numbers in the given text, with the default scanner
+/// settings.
+#[wasm_bindgen(js_name = findCandidates)]
+pub fn find_candidates(text: &str) -> Vec<Candidate> {
+    let mut byte_pos = 0;
+    let mut utf16_pos = 0;
+    let mut to_utf16 = |byte: usize| {
+        utf16_pos += text[byte_pos..byte].encode_utf16().count();
+        byte_pos = byte;
+        utf16_pos
+    };
+    crate::scan::find_candidates(text, &Default::default())
+        .into_iter()
+        .map(|m| Candidate {
+            start: to_utf16(m.range.start),
+            end: to_utf16(m.range.end),
+            digits: m.digits,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `JsError` can only be built on wasm targets, so these stick to the
+    // successful paths.
+    #[test]
+    fn exports_work() {
+        assert!(valid("4111111111111111"));
+        assert!(!valid("4111111111111112"));
+        assert!(!valid(""));
+        assert!(!valid("abc"));
+        assert!(!valid("4111 1111 1111 1111"));
+        assert_eq!(checksum("US037833100").ok(), Some("5".to_string()));
+        assert_eq!(
+            with_checksum("7992739871").ok(),
+            Some("79927398713".to_string())
+        );
+    }
+
+    #[test]
+    fn finds_candidates_at_utf16_indices() {
+        let found = find_candidates("é💳 4111 1111 1111 1111");
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].start(), found[0].end()), (4, 23));
+        assert_eq!(found[0].digits(), "4111111111111111");
+    }
+}